@@ -26,17 +26,54 @@ fn do_bench(c: &mut Criterion, group_name: &str, float: impl ryu::Float + Displa
     group.finish();
 }
 
+fn do_bench_parse<F: ryu::Float + std::str::FromStr>(c: &mut Criterion, group_name: &str, s: &str)
+where
+    F::Err: std::fmt::Debug,
+{
+    let mut group = c.benchmark_group(group_name);
+    group.bench_function("ryu", |b| {
+        b.iter(|| {
+            let bytes = hint::black_box(s.as_bytes());
+            let float: F = ryu::parse(bytes).unwrap();
+            hint::black_box(float);
+        });
+    });
+    group.bench_function("std::str", |b| {
+        b.iter(|| {
+            let s = hint::black_box(s);
+            let float: F = s.parse().unwrap();
+            hint::black_box(float);
+        });
+    });
+    group.finish();
+}
+
 fn bench(c: &mut Criterion) {
     do_bench(c, "f64[0]", 0f64);
     do_bench(c, "f64[short]", 0.1234f64);
     do_bench(c, "f64[e]", f64::consts::E);
     do_bench(c, "f64[max]", f64::MAX);
+    do_bench(c, "f64[int]", 1234f64);
+    do_bench(c, "f64[timestamp]", 1_700_000_000_123f64);
 
     do_bench(c, "f32[0]", 0f32);
     do_bench(c, "f32[short]", 0.1234f32);
     do_bench(c, "f32[e]", f32::consts::E);
     do_bench(c, "f32[max]", f32::MAX);
+    do_bench(c, "f32[int]", 1234f32);
+}
+
+fn bench_parse(c: &mut Criterion) {
+    do_bench_parse::<f64>(c, "parse f64[short]", "0.1234");
+    do_bench_parse::<f64>(c, "parse f64[e]", "2.718281828459045");
+    do_bench_parse::<f64>(c, "parse f64[max]", "1.7976931348623157e308");
+    do_bench_parse::<f64>(c, "parse f64[min]", "5e-324");
+    do_bench_parse::<f64>(c, "parse f64[int]", "1234");
+
+    do_bench_parse::<f32>(c, "parse f32[short]", "0.1234");
+    do_bench_parse::<f32>(c, "parse f32[e]", "2.7182817");
+    do_bench_parse::<f32>(c, "parse f32[max]", "3.4028235e38");
 }
 
-criterion_group!(benches, bench);
+criterion_group!(benches, bench, bench_parse);
 criterion_main!(benches);
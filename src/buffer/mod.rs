@@ -1,6 +1,12 @@
+use crate::decimal::{self, Decimal, Repr};
+use crate::fp8;
+use crate::parse::{Error, Exactness, Rounding};
+use crate::rational;
 use crate::raw;
+use crate::s2d::{self, s2d, s2d_exactness};
+use crate::s2f::{self, s2f, s2f_exactness};
 use core::mem::MaybeUninit;
-use core::{slice, str};
+use core::{fmt, slice, str};
 #[cfg(feature = "no-panic")]
 use no_panic::no_panic;
 
@@ -17,8 +23,15 @@ const NEG_INFINITY: &str = "-inf";
 /// let printed = buffer.format_finite(1.234);
 /// assert_eq!(printed, "1.234");
 /// ```
+///
+/// A `Buffer` is 25 bytes. The shortest representation printed by
+/// [`format`][Buffer::format] takes at most 24 of them; the extra byte is for
+/// the 18-digit mantissas that [`format_rounded`][Buffer::format_rounded] and
+/// [`Decimal::new_rounded`] can produce.
 pub struct Buffer {
-    bytes: [MaybeUninit<u8>; 24],
+    // 24 bytes for anything format prints, and one more for the 18-digit
+    // mantissas of format_rounded.
+    bytes: [MaybeUninit<u8>; 25],
 }
 
 impl Buffer {
@@ -27,7 +40,7 @@ impl Buffer {
     #[inline]
     #[cfg_attr(feature = "no-panic", no_panic)]
     pub fn new() -> Self {
-        let bytes = [MaybeUninit::<u8>::uninit(); 24];
+        let bytes = [MaybeUninit::<u8>::uninit(); 25];
         Buffer { bytes }
     }
 
@@ -77,6 +90,133 @@ impl Buffer {
             str::from_utf8_unchecked(slice)
         }
     }
+
+    /// Print a [`Decimal`] that was computed ahead of time into this buffer and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// The result is the same as calling [`format`][Buffer::format] on the
+    /// original floating point number, but without converting it to decimal
+    /// again.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn format_decimal(&mut self, decimal: &Decimal) -> &str {
+        unsafe {
+            let n = decimal.write(self.bytes.as_mut_ptr().cast::<u8>());
+            debug_assert!(n <= self.bytes.len());
+            let slice = slice::from_raw_parts(self.bytes.as_ptr().cast::<u8>(), n);
+            str::from_utf8_unchecked(slice)
+        }
+    }
+
+    /// Print the shortest decimal on one side of a floating point number into
+    /// this buffer and return a reference to its string representation within
+    /// the buffer.
+    ///
+    /// With [`Rounding::Downward`] the printed decimal, read as an exact value,
+    /// is never greater than `f`, and with [`Rounding::Upward`] never less;
+    /// [`Rounding::TowardZero`] never exceeds it in magnitude. This is what
+    /// printing the bounds of an interval needs, where [`format`] may land on
+    /// either side. [`Rounding::NearestEven`] prints the same as [`format`].
+    ///
+    /// The result is the shortest such decimal that still parses back to `f`.
+    /// Only half of the rounding interval is available to choose from, so a
+    /// few percent of f64 values, mostly ones with a leading digit of 1, need
+    /// 18 digits, one more than [`format`] ever prints. [`parse`] rejects those,
+    /// but [`parse_rounded`] with the opposite direction reads them back. NaN
+    /// and the infinities are printed as by [`format`].
+    ///
+    /// [`format`]: Buffer::format
+    /// [`parse`]: crate::parse
+    /// [`parse_rounded`]: crate::parse_rounded
+    /// [`Rounding::Downward`]: crate::Rounding::Downward
+    /// [`Rounding::Upward`]: crate::Rounding::Upward
+    /// [`Rounding::TowardZero`]: crate::Rounding::TowardZero
+    /// [`Rounding::NearestEven`]: crate::Rounding::NearestEven
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use ryu::Rounding;
+    ///
+    /// let mut buffer = ryu::Buffer::new();
+    /// assert_eq!(buffer.format(0.1), "0.1");
+    /// assert_eq!(buffer.format_rounded(0.1, Rounding::Downward), "0.1");
+    /// assert_eq!(buffer.format_rounded(0.1, Rounding::Upward), "0.10000000000000001");
+    /// assert_eq!(buffer.format_rounded(-0.1, Rounding::Upward), "-0.1");
+    /// assert_eq!(buffer.format(1.0715660391465826e-75), "1.0715660391465826e-75");
+    /// assert_eq!(
+    ///     buffer.format_rounded(1.0715660391465826e-75, Rounding::Downward),
+    ///     "1.07156603914658258e-75",
+    /// );
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn format_rounded<F: Float>(&mut self, f: F, rounding: Rounding) -> &str {
+        self.format_decimal(&Decimal::new_rounded(f, rounding))
+    }
+
+    /// Print an [OCP FP8] E4M3 value, given by its bit pattern, into this
+    /// buffer and return a reference to its string representation within the
+    /// buffer.
+    ///
+    /// The output is the shortest decimal that [`ryu::parse_e4m3`] rounds back
+    /// to the same value, laid out the same way as f32 output. With only three
+    /// mantissa bits this is often shorter than the exact value: the largest
+    /// E4M3 value, 448, prints as "450.0". Both NaN bit patterns, `0x7f` and
+    /// `0xff`, are formatted as "NaN". E4M3 has no infinities.
+    ///
+    /// [OCP FP8]: https://www.opencompute.org/documents/ocp-8-bit-floating-point-specification-ofp8-revision-1-0-2023-12-01-pdf-1
+    /// [`ryu::parse_e4m3`]: crate::parse_e4m3
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut buffer = ryu::Buffer::new();
+    /// assert_eq!(buffer.format_e4m3(0x1d), "0.1");
+    /// assert_eq!(buffer.format_e4m3(0xfe), "-450.0");
+    /// ```
+    #[cfg_attr(feature = "no-panic", inline)]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn format_e4m3(&mut self, bits: u8) -> &str {
+        self.format_fp8(&fp8::E4M3, bits)
+    }
+
+    /// Print an [OCP FP8] E5M2 value, given by its bit pattern, into this
+    /// buffer and return a reference to its string representation within the
+    /// buffer.
+    ///
+    /// The output is the shortest decimal that [`ryu::parse_e5m2`] rounds back
+    /// to the same value, laid out the same way as f32 output. NaN is
+    /// formatted as "NaN" and the infinities as "inf" and "-inf".
+    ///
+    /// [OCP FP8]: https://www.opencompute.org/documents/ocp-8-bit-floating-point-specification-ofp8-revision-1-0-2023-12-01-pdf-1
+    /// [`ryu::parse_e5m2`]: crate::parse_e5m2
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let mut buffer = ryu::Buffer::new();
+    /// assert_eq!(buffer.format_e5m2(0x2e), "0.1");
+    /// assert_eq!(buffer.format_e5m2(0x7b), "60000.0");
+    /// ```
+    #[cfg_attr(feature = "no-panic", inline)]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn format_e5m2(&mut self, bits: u8) -> &str {
+        self.format_fp8(&fp8::E5M2, bits)
+    }
+
+    #[inline]
+    fn format_fp8(&mut self, format: &fp8::Format, bits: u8) -> &str {
+        if format.is_nonfinite(bits) {
+            return format.format_nonfinite(bits);
+        }
+        unsafe {
+            let n = format.format_finite(bits, self.bytes.as_mut_ptr().cast::<u8>());
+            debug_assert!(n <= self.bytes.len());
+            let slice = slice::from_raw_parts(self.bytes.as_ptr().cast::<u8>(), n);
+            str::from_utf8_unchecked(slice)
+        }
+    }
 }
 
 impl Copy for Buffer {}
@@ -106,13 +246,29 @@ pub trait Float: Sealed {}
 impl Float for f32 {}
 impl Float for f64 {}
 
-pub trait Sealed: Copy {
+pub trait Sealed: Copy + fmt::Display {
+    // Upper bound on the number of bytes written by write_to_ryu_buffer.
+    const MAX_LEN: usize;
+    const NAN: Self;
+    // Lets a const fn, which cannot call trait methods, tell f32 from f64.
+    const IS_F32: bool;
     fn is_nonfinite(self) -> bool;
     fn format_nonfinite(self) -> &'static str;
+    fn decompose(self) -> Repr;
+    fn decompose_rounded(self, rounding: Rounding) -> Repr;
+    fn parse_bytes(bytes: &[u8]) -> Result<Self, Error>;
+    fn parse_bytes_exactness(bytes: &[u8], rounding: Rounding) -> Result<(Self, Exactness), Error>;
+    fn from_decimal(mantissa: u64, exponent: i32, negative: bool) -> Self;
+    fn simplest_fraction(self) -> Option<(i64, u64)>;
     unsafe fn write_to_ryu_buffer(self, result: *mut u8) -> usize;
+    unsafe fn write_fraction(self, result: *mut u8) -> usize;
 }
 
 impl Sealed for f32 {
+    const MAX_LEN: usize = 16;
+    const NAN: Self = f32::NAN;
+    const IS_F32: bool = true;
+
     #[inline]
     fn is_nonfinite(self) -> bool {
         const EXP_MASK: u32 = 0x7f800000;
@@ -135,13 +291,70 @@ impl Sealed for f32 {
         }
     }
 
+    #[inline]
+    fn decompose(self) -> Repr {
+        if self.is_nonfinite() {
+            Repr::Nonfinite(self.format_nonfinite())
+        } else {
+            decimal::decompose32(self)
+        }
+    }
+
+    #[inline]
+    fn decompose_rounded(self, rounding: Rounding) -> Repr {
+        if self.is_nonfinite() {
+            Repr::Nonfinite(self.format_nonfinite())
+        } else {
+            decimal::decompose32_rounded(self, rounding)
+        }
+    }
+
+    #[inline]
+    fn parse_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match bytes {
+            b"NaN" => Ok(Self::NAN),
+            b"inf" => Ok(Self::INFINITY),
+            b"-inf" => Ok(Self::NEG_INFINITY),
+            _ => s2f(bytes),
+        }
+    }
+
+    #[inline]
+    fn parse_bytes_exactness(bytes: &[u8], rounding: Rounding) -> Result<(Self, Exactness), Error> {
+        match bytes {
+            b"NaN" => Ok((Self::NAN, Exactness::Exact)),
+            b"inf" => Ok((Self::INFINITY, Exactness::Exact)),
+            b"-inf" => Ok((Self::NEG_INFINITY, Exactness::Exact)),
+            _ => s2f_exactness(bytes, rounding),
+        }
+    }
+
+    #[inline]
+    fn from_decimal(mantissa: u64, exponent: i32, negative: bool) -> Self {
+        s2f::from_decimal(mantissa, exponent, negative)
+    }
+
+    #[inline]
+    fn simplest_fraction(self) -> Option<(i64, u64)> {
+        rational::simplest_fraction32(self)
+    }
+
     #[inline]
     unsafe fn write_to_ryu_buffer(self, result: *mut u8) -> usize {
         raw::format32(self, result)
     }
+
+    #[inline]
+    unsafe fn write_fraction(self, result: *mut u8) -> usize {
+        raw::format32_fraction(self, result)
+    }
 }
 
 impl Sealed for f64 {
+    const MAX_LEN: usize = 24;
+    const NAN: Self = f64::NAN;
+    const IS_F32: bool = false;
+
     #[inline]
     fn is_nonfinite(self) -> bool {
         const EXP_MASK: u64 = 0x7ff0000000000000;
@@ -164,8 +377,61 @@ impl Sealed for f64 {
         }
     }
 
+    #[inline]
+    fn decompose(self) -> Repr {
+        if self.is_nonfinite() {
+            Repr::Nonfinite(self.format_nonfinite())
+        } else {
+            decimal::decompose64(self)
+        }
+    }
+
+    #[inline]
+    fn decompose_rounded(self, rounding: Rounding) -> Repr {
+        if self.is_nonfinite() {
+            Repr::Nonfinite(self.format_nonfinite())
+        } else {
+            decimal::decompose64_rounded(self, rounding)
+        }
+    }
+
+    #[inline]
+    fn parse_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match bytes {
+            b"NaN" => Ok(Self::NAN),
+            b"inf" => Ok(Self::INFINITY),
+            b"-inf" => Ok(Self::NEG_INFINITY),
+            _ => s2d(bytes),
+        }
+    }
+
+    #[inline]
+    fn parse_bytes_exactness(bytes: &[u8], rounding: Rounding) -> Result<(Self, Exactness), Error> {
+        match bytes {
+            b"NaN" => Ok((Self::NAN, Exactness::Exact)),
+            b"inf" => Ok((Self::INFINITY, Exactness::Exact)),
+            b"-inf" => Ok((Self::NEG_INFINITY, Exactness::Exact)),
+            _ => s2d_exactness(bytes, rounding),
+        }
+    }
+
+    #[inline]
+    fn from_decimal(mantissa: u64, exponent: i32, negative: bool) -> Self {
+        s2d::from_decimal(mantissa, exponent, negative)
+    }
+
+    #[inline]
+    fn simplest_fraction(self) -> Option<(i64, u64)> {
+        rational::simplest_fraction64(self)
+    }
+
     #[inline]
     unsafe fn write_to_ryu_buffer(self, result: *mut u8) -> usize {
         raw::format64(self, result)
     }
+
+    #[inline]
+    unsafe fn write_fraction(self, result: *mut u8) -> usize {
+        raw::format64_fraction(self, result)
+    }
 }
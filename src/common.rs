@@ -61,7 +61,7 @@ pub fn log2_pow5(e: i32) -> i32 /* or u32 -> u32 */ {
 
 // Returns e == 0 ? 1 : ceil(log_2(5^e)); requires 0 <= e <= 3528.
 #[cfg_attr(feature = "no-panic", inline)]
-pub fn pow5bits(e: i32) -> i32 /* or u32 -> u32 */ {
+pub const fn pow5bits(e: i32) -> i32 /* or u32 -> u32 */ {
     // This approximation works up to the point that the multiplication
     // overflows at e = 3529. If the multiplication were done in 64 bits, it
     // would fail at 5^4004 which is just greater than 2^9297.
@@ -0,0 +1,218 @@
+use crate::d2s::{DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS};
+use crate::f2s::{FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+use crate::schubfach::{d2d_const, f2d_const};
+use crate::Float;
+use core::{slice, str};
+
+/// Print a floating point number at compile time.
+///
+/// Returns a buffer holding the same text as [`Buffer::format`], including
+/// "NaN", "inf" and "-inf" for the special cases, and the number of bytes of it
+/// in use. This is a `const fn`, so it can be evaluated into a constant; the
+/// [`const_format!`] macro wraps it up as a `&'static str`.
+///
+/// All of the work is integer arithmetic, with the powers of ten that
+/// [`Buffer::format`] finds in lookup tables computed from scratch instead.
+/// It is correspondingly slower when called at runtime.
+///
+/// [`Buffer::format`]: crate::Buffer::format
+/// [`const_format!`]: crate::const_format!
+///
+/// ## Example
+///
+/// ```
+/// const FORMATTED: ([u8; 24], usize) = ryu::format_const(-1.5e-10f32);
+///
+/// let (bytes, len) = FORMATTED;
+/// assert_eq!(&bytes[..len], b"-1.5e-10");
+/// ```
+pub const fn format_const<F: Float>(f: F) -> ([u8; 24], usize) {
+    // A const fn cannot call trait methods, so the bits are read through a
+    // union instead of with to_bits.
+    let bits = Bits { float: f };
+    let (sign, ieee_mantissa, ieee_exponent, exponent_bits) = if F::IS_F32 {
+        let bits = unsafe { bits.bits32 };
+        (
+            bits >> 31 != 0,
+            (bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1)) as u64,
+            (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1),
+            FLOAT_EXPONENT_BITS,
+        )
+    } else {
+        let bits = unsafe { bits.bits64 };
+        (
+            bits >> 63 != 0,
+            bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1),
+            (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1),
+            DOUBLE_EXPONENT_BITS,
+        )
+    };
+
+    let mut out = ([0u8; 24], 0);
+    if ieee_exponent == (1u32 << exponent_bits) - 1 {
+        let s: &str = if ieee_mantissa != 0 {
+            "NaN"
+        } else if sign {
+            "-inf"
+        } else {
+            "inf"
+        };
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            out = push(out, bytes[i]);
+            i += 1;
+        }
+        return out;
+    }
+
+    if sign {
+        out = push(out, b'-');
+    }
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        out = push(out, b'0');
+        out = push(out, b'.');
+        return push(out, b'0');
+    }
+
+    if F::IS_F32 {
+        let v = f2d_const(ieee_mantissa as u32, ieee_exponent);
+        write_decimal(out, v.mantissa as u64, v.exponent, 13, -6)
+    } else {
+        let v = d2d_const(ieee_mantissa, ieee_exponent);
+        write_decimal(out, v.mantissa, v.exponent, 16, -5)
+    }
+}
+
+// Reinterprets an f32 or f64 as its bits; whichever field matches F::IS_F32.
+union Bits<F: Copy> {
+    float: F,
+    bits32: u32,
+    bits64: u64,
+}
+
+const fn push(mut out: ([u8; 24], usize), byte: u8) -> ([u8; 24], usize) {
+    out.0[out.1] = byte;
+    out.1 += 1;
+    out
+}
+
+// Lays out the unsigned shortest decimal mantissa * 10^exponent the same as
+// write_decimal64 and write_decimal32, which differ only in the thresholds for
+// switching to scientific notation.
+const fn write_decimal(
+    mut out: ([u8; 24], usize),
+    mantissa: u64,
+    exponent: i32,
+    max_kk: isize,
+    min_kk: isize,
+) -> ([u8; 24], usize) {
+    // The digits of the mantissa, most significant first.
+    let mut digits = [0u8; 20];
+    let mut length = 0;
+    let mut rest = mantissa;
+    while rest != 0 {
+        digits[19 - length] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        length += 1;
+    }
+    let first = 20 - length;
+    let length = length as isize;
+    let k = exponent as isize;
+    let kk = length + k; // 10^(kk-1) <= v < 10^kk
+
+    let mut i = 0;
+    if 0 <= k && kk <= max_kk {
+        // 1234e7 -> 12340000000.0
+        while i < kk {
+            out = push(
+                out,
+                if i < length {
+                    digits[first + i as usize]
+                } else {
+                    b'0'
+                },
+            );
+            i += 1;
+        }
+        out = push(out, b'.');
+        push(out, b'0')
+    } else if 0 < kk && kk <= max_kk {
+        // 1234e-2 -> 12.34
+        while i < length {
+            if i == kk {
+                out = push(out, b'.');
+            }
+            out = push(out, digits[first + i as usize]);
+            i += 1;
+        }
+        out
+    } else if min_kk < kk && kk <= 0 {
+        // 1234e-6 -> 0.001234
+        out = push(out, b'0');
+        out = push(out, b'.');
+        while i < -kk {
+            out = push(out, b'0');
+            i += 1;
+        }
+        while i < length - kk {
+            out = push(out, digits[first + (i + kk) as usize]);
+            i += 1;
+        }
+        out
+    } else {
+        // 1e30, 1234e30 -> 1.234e33
+        while i < length {
+            if i == 1 {
+                out = push(out, b'.');
+            }
+            out = push(out, digits[first + i as usize]);
+            i += 1;
+        }
+        out = push(out, b'e');
+        let e = kk - 1;
+        if e < 0 {
+            out = push(out, b'-');
+        }
+        let e = e.unsigned_abs();
+        if e >= 100 {
+            out = push(out, b'0' + (e / 100) as u8);
+        }
+        if e >= 10 {
+            out = push(out, b'0' + (e / 10 % 10) as u8);
+        }
+        push(out, b'0' + (e % 10) as u8)
+    }
+}
+
+#[doc(hidden)]
+pub const fn as_str(formatted: &([u8; 24], usize)) -> &str {
+    let (bytes, len) = formatted;
+    unsafe { str::from_utf8_unchecked(slice::from_raw_parts(bytes.as_ptr(), *len)) }
+}
+
+/// Print a floating point number into a `&'static str` at compile time.
+///
+/// The argument must be a constant expression of type f64 or f32. An
+/// unsuffixed literal is an f64. The text is the same as [`Buffer::format`]
+/// would produce at runtime; see [`format_const`] for how it is computed.
+///
+/// [`Buffer::format`]: crate::Buffer::format
+///
+/// ## Example
+///
+/// ```
+/// const HEADER_VERSION: f64 = 1.5e-3;
+/// const HEADER: &str = ryu::const_format!(HEADER_VERSION);
+///
+/// assert_eq!(HEADER, "0.0015");
+/// assert_eq!(ryu::const_format!(0.3f32), "0.3");
+/// assert_eq!(ryu::const_format!(1e100), "1e100");
+/// ```
+#[macro_export]
+macro_rules! const_format {
+    ($f:expr $(,)?) => {{
+        const FORMATTED: ([u8; 24], usize) = $crate::format_const($f);
+        $crate::__private::as_str(&FORMATTED)
+    }};
+}
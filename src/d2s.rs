@@ -19,12 +19,14 @@
 // KIND, either express or implied.
 
 use crate::common::{log10_pow2, log10_pow5, pow5bits};
-#[cfg(not(feature = "small"))]
+#[cfg(not(any(feature = "small", feature = "tiny")))]
 pub use crate::d2s_full_table::{DOUBLE_POW5_INV_SPLIT, DOUBLE_POW5_SPLIT};
+#[cfg(feature = "tiny")]
+pub use crate::d2s_intrinsics::{compute_inv_pow5, compute_pow5};
 use crate::d2s_intrinsics::{
     div10, div100, div5, mul_shift_all_64, multiple_of_power_of_2, multiple_of_power_of_5,
 };
-#[cfg(feature = "small")]
+#[cfg(all(feature = "small", not(feature = "tiny")))]
 pub use crate::d2s_small_table::{compute_inv_pow5, compute_pow5};
 use core::mem::MaybeUninit;
 
@@ -79,7 +81,21 @@ pub fn decimal_length17(v: u64) -> u32 {
     }
 }
 
+// Like decimal_length17, but also allows the 18-digit mantissas of
+// d2d_directed.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decimal_length18(v: u64) -> u32 {
+    debug_assert!(v < 1000000000000000000);
+
+    if v >= 100000000000000000 {
+        18
+    } else {
+        decimal_length17(v)
+    }
+}
+
 // A floating decimal representing m * 10^e.
+#[derive(Copy, Clone, Debug)]
 pub struct FloatingDecimal64 {
     pub mantissa: u64,
     // Decimal exponent's range is -324 to 308
@@ -89,6 +105,24 @@ pub struct FloatingDecimal64 {
 
 #[cfg_attr(feature = "no-panic", inline)]
 pub fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
+    d2d_shortest(d2d_interval(ieee_mantissa, ieee_exponent))
+}
+
+// The decimal interval of valid representations computed by steps 1-3, scaled
+// by 10^e10.
+#[derive(Copy, Clone)]
+pub struct Interval64 {
+    vr: u64,
+    vp: u64,
+    vm: u64,
+    e10: i32,
+    accept_bounds: bool,
+    vm_is_trailing_zeros: bool,
+    vr_is_trailing_zeros: bool,
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+fn d2d_interval(ieee_mantissa: u64, ieee_exponent: u32) -> Interval64 {
     let (e2, m2) = if ieee_exponent == 0 {
         (
             // We subtract 2 so that the bounds computation has 2 additional bits.
@@ -113,9 +147,9 @@ pub fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
     // uint64_t mm = mv - 1 - mm_shift;
 
     // Step 3: Convert to a decimal power base using 128-bit arithmetic.
-    let mut vr: u64;
+    let vr: u64;
     let mut vp: u64;
-    let mut vm: u64;
+    let vm: u64;
     let mut vp_uninit: MaybeUninit<u64> = MaybeUninit::uninit();
     let mut vm_uninit: MaybeUninit<u64> = MaybeUninit::uninit();
     let e10: i32;
@@ -131,9 +165,9 @@ pub fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
         vr = unsafe {
             mul_shift_all_64(
                 m2,
-                #[cfg(feature = "small")]
+                #[cfg(any(feature = "small", feature = "tiny"))]
                 &compute_inv_pow5(q),
-                #[cfg(not(feature = "small"))]
+                #[cfg(not(any(feature = "small", feature = "tiny")))]
                 {
                     debug_assert!(q < DOUBLE_POW5_INV_SPLIT.len() as u32);
                     DOUBLE_POW5_INV_SPLIT.get_unchecked(q as usize)
@@ -173,9 +207,9 @@ pub fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
         vr = unsafe {
             mul_shift_all_64(
                 m2,
-                #[cfg(feature = "small")]
+                #[cfg(any(feature = "small", feature = "tiny"))]
                 &compute_pow5(i as u32),
-                #[cfg(not(feature = "small"))]
+                #[cfg(not(any(feature = "small", feature = "tiny")))]
                 {
                     debug_assert!(i < DOUBLE_POW5_SPLIT.len() as i32);
                     DOUBLE_POW5_SPLIT.get_unchecked(i as usize)
@@ -209,6 +243,29 @@ pub fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
         }
     }
 
+    Interval64 {
+        vr,
+        vp,
+        vm,
+        e10,
+        accept_bounds,
+        vm_is_trailing_zeros,
+        vr_is_trailing_zeros,
+    }
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+fn d2d_shortest(interval: Interval64) -> FloatingDecimal64 {
+    let Interval64 {
+        mut vr,
+        mut vp,
+        mut vm,
+        e10,
+        accept_bounds,
+        mut vm_is_trailing_zeros,
+        mut vr_is_trailing_zeros,
+    } = interval;
+
     // Step 4: Find the shortest decimal representation in the interval of valid representations.
     let mut removed = 0i32;
     let mut last_removed_digit = 0u8;
@@ -300,3 +357,100 @@ pub fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
         mantissa: output,
     }
 }
+
+// Like d2d, but picks the shortest decimal on one side of the value instead of
+// the one nearest to it: the largest in magnitude that does not exceed the
+// exact value if round_up is false, the smallest that is not below it if
+// round_up is true. The result still lies in the rounding interval, so it
+// parses back to the same double. Half of the interval is always wider than
+// the spacing of 18-digit decimals, but not always wider than that of 17-digit
+// ones, so the mantissa may have 18 digits where d2d never needs more than 17.
+// That only happens for mantissas below about 10 * 2^54, or 1.8 * 10^17, where
+// the 17-digit spacing can exceed half of the interval.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn d2d_directed(ieee_mantissa: u64, ieee_exponent: u32, round_up: bool) -> FloatingDecimal64 {
+    let Interval64 {
+        mut vr,
+        mut vp,
+        mut vm,
+        e10,
+        accept_bounds,
+        ..
+    } = d2d_interval(ieee_mantissa, ieee_exponent);
+
+    let (e2, m2) = if ieee_exponent == 0 {
+        (
+            1 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32 - 2,
+            ieee_mantissa,
+        )
+    } else {
+        (
+            ieee_exponent as i32 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32 - 2,
+            (1u64 << DOUBLE_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    let mv = 4 * m2;
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    let mm = mv - 1 - mm_shift;
+
+    // d2d_interval only tracks whether vr and vm are exact where the shortest
+    // search needs it. Here we need to know for every input whether
+    // {vr,vm} * 10^e10 is {mv,mm} * 2^e2 exactly, with no digits truncated.
+    let (mut vr_is_exact, mut vm_is_exact) = if e2 >= 0 {
+        let q = e10 as u32;
+        // d2d_interval steps vp back from an excluded upper bound only where
+        // the shortest search could land on it. Cover the remaining cases.
+        if !accept_bounds && (q > 21 || mv % 5 == 0) && multiple_of_power_of_5(mv + 2, q) {
+            vp -= 1;
+        }
+        (multiple_of_power_of_5(mv, q), multiple_of_power_of_5(mm, q))
+    } else {
+        let q = (e10 - e2) as u32;
+        (
+            q < 64 && multiple_of_power_of_2(mv, q),
+            q < 64 && multiple_of_power_of_2(mm, q),
+        )
+    };
+
+    let mut removed = 0i32;
+    let output = if round_up {
+        // Remove digits while rounding vr up still lands within vp.
+        loop {
+            let vr_div10 = div10(vr);
+            let vr_mod10 = (vr as u32).wrapping_sub(10u32.wrapping_mul(vr_div10 as u32));
+            let vp_div10 = div10(vp);
+            let exact = vr_is_exact && vr_mod10 == 0;
+            if vr_div10 + !exact as u64 > vp_div10 {
+                break;
+            }
+            vr_is_exact = exact;
+            vr = vr_div10;
+            vp = vp_div10;
+            removed += 1;
+        }
+        vr + !vr_is_exact as u64
+    } else {
+        // Remove digits while truncated vr still lies above vm, or on vm if it
+        // is an exact bound that belongs to the interval.
+        loop {
+            let vr_div10 = div10(vr);
+            let vm_div10 = div10(vm);
+            let vm_mod10 = (vm as u32).wrapping_sub(10u32.wrapping_mul(vm_div10 as u32));
+            let exact = vm_is_exact && vm_mod10 == 0;
+            if vr_div10 <= vm_div10 && !(vr_div10 == vm_div10 && accept_bounds && exact) {
+                break;
+            }
+            vm_is_exact = exact;
+            vr = vr_div10;
+            vm = vm_div10;
+            removed += 1;
+        }
+        vr
+    };
+    let exp = e10 + removed;
+
+    FloatingDecimal64 {
+        exponent: exp,
+        mantissa: output,
+    }
+}
@@ -87,3 +87,233 @@ pub unsafe fn mul_shift_all_64(
     ptr::write(vm, mul_shift_64(4 * m - 1 - mm_shift as u64, mul, j));
     mul_shift_64(4 * m, mul, j)
 }
+
+// Multiple-precision powers of 5, built from 64-bit limbs and 128-bit
+// products. With the "tiny" feature these stand in for DOUBLE_POW5_SPLIT and
+// DOUBLE_POW5_INV_SPLIT, and they are what const formatting uses in place of
+// every table, since a const fn cannot read a static. 5^341, the largest power
+// needed, has 792 bits, or 13 limbs, and products with a u128 take 15.
+//
+// Arrays have a power of two length and every index is masked to it, so that
+// the compiler can see they are all in bounds without any checks that could
+// panic. The masking never changes an index.
+const LIMBS: usize = 16;
+const MASK: usize = LIMBS - 1;
+
+#[derive(Copy, Clone)]
+pub struct BigPow5 {
+    // Little-endian, with `len` limbs in use.
+    limbs: [u64; LIMBS],
+    len: usize,
+    bits: usize,
+}
+
+impl BigPow5 {
+    #[cfg_attr(feature = "no-panic", inline)]
+    pub const fn new(mut i: u32) -> Self {
+        // The largest power of 5 that fits in a u64.
+        const POW5_27: u64 = 7450580596923828125;
+
+        let mut limbs = [0u64; LIMBS];
+        limbs[0] = 1;
+        let mut pow5 = BigPow5 {
+            limbs,
+            len: 1,
+            bits: 1,
+        };
+        while i > 0 {
+            let step = if i >= 27 { 27 } else { i };
+            let factor = if step == 27 { POW5_27 } else { 5u64.pow(step) };
+            pow5 = pow5.mul(factor);
+            i -= step;
+        }
+        pow5
+    }
+
+    #[cfg_attr(feature = "no-panic", inline)]
+    const fn mul(mut self, factor: u64) -> Self {
+        let mut carry = 0u64;
+        let mut j = 0;
+        while j < self.len {
+            let product = self.limbs[j & MASK] as u128 * factor as u128 + carry as u128;
+            self.limbs[j & MASK] = product as u64;
+            carry = (product >> 64) as u64;
+            j += 1;
+        }
+        if carry != 0 {
+            self.limbs[self.len & MASK] = carry;
+            self.len += 1;
+        }
+        self.bits = 64 * self.len - self.limbs[(self.len - 1) & MASK].leading_zeros() as usize;
+        self
+    }
+
+    #[cfg_attr(feature = "no-panic", inline)]
+    const fn limb(&self, j: usize) -> u64 {
+        if j < self.len {
+            self.limbs[j & MASK]
+        } else {
+            0
+        }
+    }
+
+    // The top 128 bits of 5^i, shifted so that the highest bit is set.
+    #[cfg_attr(feature = "no-panic", inline)]
+    pub const fn top128(&self) -> u128 {
+        let low = self.limb(0) as u128 | (self.limb(1) as u128) << 64;
+        if self.bits <= 128 {
+            return low << (128 - self.bits);
+        }
+        // Bits [shift, shift + 128) of 5^i, spread over at most 3 limbs.
+        let shift = self.bits - 128;
+        let index = shift / 64;
+        let offset = shift % 64;
+        let low = self.limb(index) as u128 | (self.limb(index + 1) as u128) << 64;
+        if offset == 0 {
+            low
+        } else {
+            low >> offset | (self.limb(index + 2) as u128) << (128 - offset)
+        }
+    }
+
+    // The top 128 bits of 5^i rounded up, which only differs from top128 when
+    // there are more than 128 bits, since 5^i is odd.
+    #[cfg_attr(feature = "no-panic", inline)]
+    pub const fn top128_ceil(&self) -> u128 {
+        self.top128() + (self.bits > 128) as u128
+    }
+
+    // floor(2^(bits - 1 + extra) / 5^i), for extra <= 128. The result has
+    // `extra` or `extra + 1` bits.
+    #[cfg_attr(feature = "no-panic", inline)]
+    pub const fn inverse(&self, extra: u32) -> u128 {
+        debug_assert!(extra <= 128 && (extra < 128 || self.bits > 1));
+
+        // Dividing by the top 128 bits of 5^i instead of all of it gives a
+        // quotient that is too large by less than 2^(extra - 127), so at most
+        // two too large.
+        let top = self.top128();
+        let mut remainder = 1u128 << 127;
+        let mut quotient = 0u128;
+        if remainder >= top {
+            remainder -= top;
+            quotient = 1;
+        }
+        let mut n = 0;
+        while n < extra {
+            let carry = remainder >> 127 != 0;
+            remainder <<= 1;
+            quotient <<= 1;
+            if carry || remainder >= top {
+                remainder = remainder.wrapping_sub(top);
+                quotient |= 1;
+            }
+            n += 1;
+        }
+        let exponent = self.bits - 1 + extra as usize;
+        while self.exceeds_pow2(quotient, exponent) {
+            quotient -= 1;
+        }
+        quotient
+    }
+
+    // Whether quotient * 5^i > 2^exponent.
+    #[cfg_attr(feature = "no-panic", inline)]
+    const fn exceeds_pow2(&self, quotient: u128, exponent: usize) -> bool {
+        let mut product = [0u64; LIMBS];
+        let mut k = 0;
+        while k < 2 {
+            let factor = (quotient >> (64 * k)) as u64;
+            let mut carry = 0u64;
+            let mut j = 0;
+            while j < self.len {
+                let sum = self.limbs[j & MASK] as u128 * factor as u128
+                    + product[(j + k) & MASK] as u128
+                    + carry as u128;
+                product[(j + k) & MASK] = sum as u64;
+                carry = (sum >> 64) as u64;
+                j += 1;
+            }
+            product[(self.len + k) & MASK] = carry;
+            k += 1;
+        }
+        // Compare limb by limb against 2^exponent, from the top.
+        let index = exponent / 64;
+        let mut j = LIMBS;
+        while j > index + 1 {
+            j -= 1;
+            if product[j & MASK] != 0 {
+                return true;
+            }
+        }
+        let high = product[index & MASK];
+        let bit = 1u64 << (exponent % 64);
+        if high != bit {
+            return high > bit;
+        }
+        let mut j = index;
+        while j > 0 {
+            j -= 1;
+            if product[j & MASK] != 0 {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+// 5^i in the form required by Ryū, the same as DOUBLE_POW5_SPLIT[i]: its top
+// 125 bits.
+#[cfg_attr(feature = "no-panic", inline)]
+pub const fn pow5_split(pow5: &BigPow5) -> (u64, u64) {
+    let value = pow5.top128() >> 3;
+    (value as u64, (value >> 64) as u64)
+}
+
+// 5^-i in the form required by Ryū, the same as DOUBLE_POW5_INV_SPLIT[i]:
+// floor(2^(pow5bits(i) - 1 + 125) / 5^i) + 1.
+#[cfg_attr(feature = "no-panic", inline)]
+pub const fn pow5_inv_split(pow5: &BigPow5) -> (u64, u64) {
+    let value = pow5.inverse(125) + 1;
+    (value as u64, (value >> 64) as u64)
+}
+
+// pow5_split of every 5^(i * step) for i < N.
+#[cfg_attr(feature = "tiny", allow(dead_code))]
+pub const fn pow5_split_table<const N: usize>(step: u32) -> [(u64, u64); N] {
+    let mut table = [(0, 0); N];
+    let mut i = 0;
+    while i < N {
+        table[i] = pow5_split(&BigPow5::new(i as u32 * step));
+        i += 1;
+    }
+    table
+}
+
+// pow5_inv_split of every 5^(i * step) for i < N.
+#[cfg_attr(feature = "tiny", allow(dead_code))]
+pub const fn pow5_inv_split_table<const N: usize>(step: u32) -> [(u64, u64); N] {
+    let mut table = [(0, 0); N];
+    let mut i = 0;
+    while i < N {
+        table[i] = pow5_inv_split(&BigPow5::new(i as u32 * step));
+        i += 1;
+    }
+    table
+}
+
+// Computes 5^i on demand, for the "tiny" feature.
+#[cfg(feature = "tiny")]
+#[cfg_attr(feature = "no-panic", inline)]
+pub unsafe fn compute_pow5(i: u32) -> (u64, u64) {
+    debug_assert!(i < 326);
+    pow5_split(&BigPow5::new(i))
+}
+
+// Computes 5^-i on demand, for the "tiny" feature.
+#[cfg(feature = "tiny")]
+#[cfg_attr(feature = "no-panic", inline)]
+pub unsafe fn compute_inv_pow5(i: u32) -> (u64, u64) {
+    debug_assert!(i < 344);
+    pow5_inv_split(&BigPow5::new(i))
+}
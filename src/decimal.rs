@@ -0,0 +1,269 @@
+use crate::buffer::Float;
+#[cfg(not(feature = "schubfach"))]
+use crate::d2s::d2d;
+use crate::d2s::{d2d_directed, FloatingDecimal64, DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS};
+#[cfg(not(feature = "schubfach"))]
+use crate::f2s::f2d;
+use crate::f2s::{f2d_directed, FloatingDecimal32, FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+use crate::parse::Rounding;
+use crate::pretty::{decimal_len32, decimal_len64, write_decimal32, write_decimal64};
+#[cfg(feature = "schubfach")]
+use crate::schubfach::{d2d, f2d};
+use core::ptr;
+#[cfg(feature = "no-panic")]
+use no_panic::no_panic;
+
+/// The shortest decimal representation of a floating point number, computed
+/// ahead of printing it.
+///
+/// Converting to decimal is the expensive part of formatting. A `Decimal`
+/// holds the result of that conversion so that the exact length of the output
+/// can be inspected, for example to reserve space or lay out fixed-width
+/// columns, and the number can then be printed without converting again.
+///
+/// ## Example
+///
+/// ```
+/// let decimal = ryu::Decimal::new(1.234e-7f64);
+/// assert_eq!(decimal.formatted_len(), 8);
+/// assert_eq!(decimal.mantissa(), 1234);
+/// assert_eq!(decimal.exponent(), -10);
+///
+/// let mut buffer = ryu::Buffer::new();
+/// assert_eq!(buffer.format_decimal(&decimal), "1.234e-7");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Decimal {
+    repr: Repr,
+    len: u8,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Repr {
+    Nonfinite(&'static str),
+    Zero { sign: bool },
+    F64 { sign: bool, v: FloatingDecimal64 },
+    F32 { sign: bool, v: FloatingDecimal32 },
+}
+
+impl Decimal {
+    /// Convert a floating point number to its shortest decimal representation.
+    ///
+    /// NaN and the infinities are accepted; they have no mantissa or exponent
+    /// and are printed as "NaN", "inf" and "-inf" like [`Buffer::format`].
+    ///
+    /// [`Buffer::format`]: crate::Buffer::format
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn new<F: Float>(f: F) -> Self {
+        Decimal::from_repr(f.decompose())
+    }
+
+    /// Convert a floating point number to the shortest decimal on the side of
+    /// it given by `rounding`. See [`Buffer::format_rounded`].
+    ///
+    /// [`Buffer::format_rounded`]: crate::Buffer::format_rounded
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn new_rounded<F: Float>(f: F, rounding: Rounding) -> Self {
+        Decimal::from_repr(f.decompose_rounded(rounding))
+    }
+
+    #[cfg_attr(feature = "no-panic", inline)]
+    fn from_repr(repr: Repr) -> Self {
+        let len = match &repr {
+            Repr::Nonfinite(s) => s.len(),
+            Repr::Zero { sign } => *sign as usize + 3,
+            Repr::F64 { sign, v } => *sign as usize + decimal_len64(*v),
+            Repr::F32 { sign, v } => *sign as usize + decimal_len32(*v),
+        };
+        Decimal {
+            repr,
+            len: len as u8,
+        }
+    }
+
+    /// The number of bytes that printing this decimal produces. This is the
+    /// length that [`Buffer::format_decimal`] writes.
+    ///
+    /// [`Buffer::format_decimal`]: crate::Buffer::format_decimal
+    #[inline]
+    pub fn formatted_len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// The decimal digits of the number as an integer, without trailing zeros
+    /// beyond what the shortest representation needs. This is 0 for zero, NaN
+    /// and the infinities.
+    #[inline]
+    pub fn mantissa(&self) -> u64 {
+        match self.repr {
+            Repr::Nonfinite(_) | Repr::Zero { .. } => 0,
+            Repr::F64 { v, .. } => v.mantissa,
+            Repr::F32 { v, .. } => v.mantissa as u64,
+        }
+    }
+
+    /// The power of 10 by which [`mantissa`][Decimal::mantissa] is scaled.
+    /// This is 0 for zero, NaN and the infinities.
+    #[inline]
+    pub fn exponent(&self) -> i32 {
+        match self.repr {
+            Repr::Nonfinite(_) | Repr::Zero { .. } => 0,
+            Repr::F64 { v, .. } => v.exponent,
+            Repr::F32 { v, .. } => v.exponent,
+        }
+    }
+
+    /// Whether the number has its sign bit set. This is false for NaN.
+    #[inline]
+    pub fn is_sign_negative(&self) -> bool {
+        match self.repr {
+            Repr::Nonfinite(s) => s.starts_with('-'),
+            Repr::Zero { sign } | Repr::F64 { sign, .. } | Repr::F32 { sign, .. } => sign,
+        }
+    }
+
+    /// Print this decimal into the beginning of a byte slice and return the
+    /// number of bytes written, which is [`formatted_len`], or `None` if the
+    /// slice is shorter than that.
+    ///
+    /// Exactly `formatted_len` bytes are written, so a slice sized with
+    /// [`formatted_len`] is always enough.
+    ///
+    /// [`formatted_len`]: Decimal::formatted_len
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// let decimal = ryu::Decimal::new(-0.3f32);
+    /// let mut out = vec![0u8; decimal.formatted_len()];
+    /// assert_eq!(decimal.write_into(&mut out), Some(4));
+    /// assert_eq!(out, b"-0.3");
+    /// ```
+    #[inline]
+    pub fn write_into(&self, out: &mut [u8]) -> Option<usize> {
+        if out.len() < self.formatted_len() {
+            return None;
+        }
+        unsafe { Some(self.write(out.as_mut_ptr())) }
+    }
+
+    // Requires that result point to at least formatted_len writable bytes.
+    #[cfg_attr(feature = "no-panic", inline)]
+    pub(crate) unsafe fn write(&self, result: *mut u8) -> usize {
+        let n = match self.repr {
+            Repr::Nonfinite(s) => {
+                ptr::copy_nonoverlapping(s.as_ptr(), result, s.len());
+                s.len()
+            }
+            Repr::Zero { sign } => {
+                if sign {
+                    *result = b'-';
+                }
+                ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.add(sign as usize), 3);
+                sign as usize + 3
+            }
+            Repr::F64 { sign, v } => {
+                if sign {
+                    *result = b'-';
+                }
+                sign as usize + write_decimal64(v, result.add(sign as usize))
+            }
+            Repr::F32 { sign, v } => {
+                if sign {
+                    *result = b'-';
+                }
+                sign as usize + write_decimal32(v, result.add(sign as usize))
+            }
+        };
+        debug_assert_eq!(n, self.formatted_len());
+        n
+    }
+}
+
+/// Compute the number of bytes that [`Buffer::format`] produces for a
+/// floating point number, without printing it.
+///
+/// This performs the full conversion to decimal. To print the number
+/// afterward without converting it a second time, use [`Decimal`].
+///
+/// [`Buffer::format`]: crate::Buffer::format
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(ryu::formatted_len(1.5f64), 3);
+/// assert_eq!(ryu::formatted_len(-1e300f64), 6);
+/// assert_eq!(ryu::formatted_len(f32::NAN), 3);
+/// ```
+#[inline]
+pub fn formatted_len<F: Float>(f: F) -> usize {
+    Decimal::new(f).formatted_len()
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decompose64(f: f64) -> Repr {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        Repr::Zero { sign }
+    } else {
+        let v = d2d(ieee_mantissa, ieee_exponent);
+        Repr::F64 { sign, v }
+    }
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decompose32(f: f32) -> Repr {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        Repr::Zero { sign }
+    } else {
+        let v = f2d(ieee_mantissa, ieee_exponent);
+        Repr::F32 { sign, v }
+    }
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decompose64_rounded(f: f64, rounding: Rounding) -> Repr {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        Repr::Zero { sign }
+    } else if rounding == Rounding::NearestEven {
+        let v = d2d(ieee_mantissa, ieee_exponent);
+        Repr::F64 { sign, v }
+    } else {
+        let round_up = rounding.away_from_zero(sign);
+        let v = d2d_directed(ieee_mantissa, ieee_exponent, round_up);
+        Repr::F64 { sign, v }
+    }
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decompose32_rounded(f: f32, rounding: Rounding) -> Repr {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        Repr::Zero { sign }
+    } else if rounding == Rounding::NearestEven {
+        let v = f2d(ieee_mantissa, ieee_exponent);
+        Repr::F32 { sign, v }
+    } else {
+        let round_up = rounding.away_from_zero(sign);
+        let v = f2d_directed(ieee_mantissa, ieee_exponent, round_up);
+        Repr::F32 { sign, v }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Buffer, Float};
+use core::fmt::{self, Write as _};
+
+/// Wrapper that implements [`core::fmt::Display`] for a floating point number
+/// using Ryū.
+///
+/// The number is printed the same way as [`Buffer::format`], and the usual
+/// formatting flags apply to the number as a whole, the same as they do for
+/// the standard library's float formatting:
+///
+/// - width, fill and alignment, as in `{:>12}` or `{:*^9}`;
+/// - `+`, which prints a plus sign for positive numbers and infinity;
+/// - `0`, which pads with zeros between the sign and the digits, as in `{:08}`.
+///
+/// Ryū always prints the shortest representation, so when a precision is
+/// given, as in `{:.3}`, formatting is delegated to the standard library's
+/// implementation for that float type instead.
+///
+/// The `Debug` impl prints the same as `Display`.
+///
+/// ## Example
+///
+/// ```
+/// use ryu::Display;
+///
+/// assert_eq!(format!("{}", Display(1e30)), "1e30");
+/// assert_eq!(format!("[{:>8}]", Display(1.5)), "[     1.5]");
+/// assert_eq!(format!("[{:+08}]", Display(1.5)), "[+00001.5]");
+/// assert_eq!(format!("[{:08}]", Display(-1.5f32)), "[-00001.5]");
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct Display<F>(pub F);
+
+impl<F: Float> fmt::Display for Display<F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if formatter.precision().is_some() {
+            return fmt::Display::fmt(&self.0, formatter);
+        }
+        let mut buffer = Buffer::new();
+        let printed = buffer.format(self.0);
+        if printed == "NaN" {
+            return pad_nan(formatter);
+        }
+        match printed.strip_prefix('-') {
+            Some(digits) => formatter.pad_integral(false, "", digits),
+            None => formatter.pad_integral(true, "", printed),
+        }
+    }
+}
+
+impl<F: Float> fmt::Debug for Display<F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, formatter)
+    }
+}
+
+// NaN never gets a sign, even with the `+` flag, but is otherwise padded like
+// a number.
+#[cold]
+fn pad_nan(formatter: &mut fmt::Formatter) -> fmt::Result {
+    const NAN: &str = "NaN";
+    let padding = formatter.width().unwrap_or(0).saturating_sub(NAN.len());
+    let (fill, pre, post) = if formatter.sign_aware_zero_pad() {
+        ('0', padding, 0)
+    } else {
+        let fill = formatter.fill();
+        match formatter.align() {
+            Some(fmt::Alignment::Left) => (fill, 0, padding),
+            Some(fmt::Alignment::Center) => (fill, padding / 2, (padding + 1) / 2),
+            Some(fmt::Alignment::Right) | None => (fill, padding, 0),
+        }
+    };
+    for _ in 0..pre {
+        formatter.write_char(fill)?;
+    }
+    formatter.write_str(NAN)?;
+    for _ in 0..post {
+        formatter.write_char(fill)?;
+    }
+    Ok(())
+}
@@ -25,10 +25,11 @@ use crate::f2s_intrinsics::{
 
 pub const FLOAT_MANTISSA_BITS: u32 = 23;
 pub const FLOAT_EXPONENT_BITS: u32 = 8;
-const FLOAT_BIAS: i32 = 127;
+pub const FLOAT_BIAS: i32 = 127;
 pub use crate::f2s_intrinsics::{FLOAT_POW5_BITCOUNT, FLOAT_POW5_INV_BITCOUNT};
 
 // A floating decimal representing m * 10^e.
+#[derive(Copy, Clone, Debug)]
 pub struct FloatingDecimal32 {
     pub mantissa: u32,
     // Decimal exponent's range is -45 to 38
@@ -50,14 +51,24 @@ pub fn f2d(ieee_mantissa: u32, ieee_exponent: u32) -> FloatingDecimal32 {
             (1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa,
         )
     };
+    // Implicit bool -> int conversion. True is 1, false is 0.
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u32;
+    f2d_parts(m2, e2, mm_shift)
+}
+
+// Shortest decimal for the binary float m2 * 2^(e2+2), where e2 already
+// includes the 2 additional bits for the bounds computation. This is the body
+// of f2d after the IEEE fields have been decoded, which lets narrower formats
+// whose values are all exactly representable as f32 (such as FP8) reuse it
+// with their own mantissa width and rounding interval.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn f2d_parts(m2: u32, e2: i32, mm_shift: u32) -> FloatingDecimal32 {
     let even = (m2 & 1) == 0;
     let accept_bounds = even;
 
     // Step 2: Determine the interval of valid decimal representations.
     let mv = 4 * m2;
     let mp = 4 * m2 + 2;
-    // Implicit bool -> int conversion. True is 1, false is 0.
-    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u32;
     let mm = 4 * m2 - 1 - mm_shift;
 
     // Step 3: Convert to a decimal power base using 64-bit arithmetic.
@@ -175,3 +186,104 @@ pub fn f2d(ieee_mantissa: u32, ieee_exponent: u32) -> FloatingDecimal32 {
         mantissa: output,
     }
 }
+
+// Like f2d, but picks the shortest decimal on one side of the value instead of
+// the one nearest to it: the largest in magnitude that does not exceed the
+// exact value if round_up is false, the smallest that is not below it if
+// round_up is true. See d2d_directed.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn f2d_directed(ieee_mantissa: u32, ieee_exponent: u32, round_up: bool) -> FloatingDecimal32 {
+    let (e2, m2) = if ieee_exponent == 0 {
+        (
+            1 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32 - 2,
+            ieee_mantissa,
+        )
+    } else {
+        (
+            ieee_exponent as i32 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32 - 2,
+            (1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u32;
+    let accept_bounds = (m2 & 1) == 0;
+
+    let mv = 4 * m2;
+    let mp = 4 * m2 + 2;
+    let mm = 4 * m2 - 1 - mm_shift;
+
+    // Same as step 3 of f2d, but tracking for each of vr, vp and vm whether it
+    // is the exact scaled value with no digits truncated.
+    let mut vr: u32;
+    let mut vp: u32;
+    let mut vm: u32;
+    let e10: i32;
+    let mut vr_is_exact: bool;
+    let mut vm_is_exact: bool;
+    let vp_is_exact: bool;
+    if e2 >= 0 {
+        let q = log10_pow2(e2);
+        e10 = q as i32;
+        let k = FLOAT_POW5_INV_BITCOUNT + pow5bits(q as i32) - 1;
+        let i = -e2 + q as i32 + k;
+        vr = mul_pow5_inv_div_pow2(mv, q, i);
+        vp = mul_pow5_inv_div_pow2(mp, q, i);
+        vm = mul_pow5_inv_div_pow2(mm, q, i);
+        vr_is_exact = multiple_of_power_of_5_32(mv, q);
+        vp_is_exact = multiple_of_power_of_5_32(mp, q);
+        vm_is_exact = multiple_of_power_of_5_32(mm, q);
+    } else {
+        let q = log10_pow5(-e2);
+        e10 = q as i32 + e2;
+        let i = -e2 - q as i32;
+        let k = pow5bits(i) - FLOAT_POW5_BITCOUNT;
+        let j = q as i32 - k;
+        vr = mul_pow5_div_pow2(mv, i as u32, j);
+        vp = mul_pow5_div_pow2(mp, i as u32, j);
+        vm = mul_pow5_div_pow2(mm, i as u32, j);
+        vr_is_exact = q < 32 && multiple_of_power_of_2_32(mv, q);
+        vp_is_exact = q < 32 && multiple_of_power_of_2_32(mp, q);
+        vm_is_exact = q < 32 && multiple_of_power_of_2_32(mm, q);
+    }
+    if vp_is_exact && !accept_bounds {
+        vp -= 1;
+    }
+
+    let mut removed = 0i32;
+    let output = if round_up {
+        // Remove digits while rounding vr up still lands within vp.
+        loop {
+            let exact = vr_is_exact && vr % 10 == 0;
+            if vr / 10 + !exact as u32 > vp / 10 && vr + !vr_is_exact as u32 <= 999999999 {
+                break;
+            }
+            vr_is_exact = exact;
+            vr /= 10;
+            vp /= 10;
+            removed += 1;
+        }
+        vr + !vr_is_exact as u32
+    } else {
+        // Remove digits while truncated vr still lies above vm, or on vm if it
+        // is an exact bound that belongs to the interval.
+        loop {
+            let exact = vm_is_exact && vm % 10 == 0;
+            if vr / 10 <= vm / 10
+                && !(vr / 10 == vm / 10 && accept_bounds && exact)
+                && vr <= 999999999
+            {
+                break;
+            }
+            vm_is_exact = exact;
+            vr /= 10;
+            vm /= 10;
+            removed += 1;
+        }
+        vr
+    };
+    let exp = e10 + removed;
+
+    FloatingDecimal32 {
+        exponent: exp,
+        mantissa: output,
+    }
+}
@@ -73,7 +73,7 @@ fn mul_shift_32(m: u32, factor: u64, shift: i32) -> u32 {
 
 #[cfg_attr(feature = "no-panic", inline)]
 pub fn mul_pow5_inv_div_pow2(m: u32, q: u32, j: i32) -> u32 {
-    #[cfg(feature = "small")]
+    #[cfg(any(feature = "small", feature = "tiny"))]
     {
         // The inverse multipliers are defined as [2^x / 5^y] + 1; the upper 64
         // bits from the double lookup table are the correct bits for [2^x /
@@ -84,7 +84,7 @@ pub fn mul_pow5_inv_div_pow2(m: u32, q: u32, j: i32) -> u32 {
         mul_shift_32(m, pow5.1 + 1, j)
     }
 
-    #[cfg(not(feature = "small"))]
+    #[cfg(not(any(feature = "small", feature = "tiny")))]
     {
         debug_assert!(q < d2s::DOUBLE_POW5_INV_SPLIT.len() as u32);
         unsafe {
@@ -99,13 +99,13 @@ pub fn mul_pow5_inv_div_pow2(m: u32, q: u32, j: i32) -> u32 {
 
 #[cfg_attr(feature = "no-panic", inline)]
 pub fn mul_pow5_div_pow2(m: u32, i: u32, j: i32) -> u32 {
-    #[cfg(feature = "small")]
+    #[cfg(any(feature = "small", feature = "tiny"))]
     {
         let pow5 = unsafe { d2s::compute_pow5(i) };
         mul_shift_32(m, pow5.1, j)
     }
 
-    #[cfg(not(feature = "small"))]
+    #[cfg(not(any(feature = "small", feature = "tiny")))]
     {
         debug_assert!(i < d2s::DOUBLE_POW5_SPLIT.len() as u32);
         unsafe { mul_shift_32(m, d2s::DOUBLE_POW5_SPLIT.get_unchecked(i as usize).1, j) }
@@ -0,0 +1,152 @@
+use crate::buffer::Float;
+use crate::write::format_into_array;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::{fmt, str};
+
+/// A printed floating point number held by value.
+///
+/// The string returned by [`Buffer::format`] borrows the buffer, so it cannot
+/// outlive it. `FormattedFloat` instead stores the printed bytes inline, in 25
+/// bytes on the stack, so it can be returned from functions and kept in
+/// structs or as a map key. It dereferences to `str`, and compares, orders and
+/// hashes the same as the string it contains.
+///
+/// [`Buffer::format`]: crate::Buffer::format
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let key = ryu::format_owned(1.5f64);
+/// assert_eq!(&*key, "1.5");
+/// assert_eq!(key.len(), 3);
+///
+/// let mut map = HashMap::new();
+/// map.insert(key, "one and a half");
+/// assert_eq!(map["1.5"], "one and a half");
+/// ```
+#[derive(Copy, Clone)]
+pub struct FormattedFloat {
+    bytes: [u8; 24],
+    len: u8,
+}
+
+/// Print a floating point number and return the output by value.
+///
+/// The output is the same as [`Buffer::format`], including "NaN", "inf" and
+/// "-inf" for the special cases.
+///
+/// [`Buffer::format`]: crate::Buffer::format
+///
+/// ## Example
+///
+/// ```
+/// fn label(f: f32) -> ryu::FormattedFloat {
+///     ryu::format_owned(f)
+/// }
+///
+/// assert_eq!(label(-0.25).to_string(), "-0.25");
+/// ```
+#[inline]
+pub fn format_owned<F: Float>(f: F) -> FormattedFloat {
+    let mut bytes = [0u8; 24];
+    let len = format_into_array(f, &mut bytes);
+    FormattedFloat {
+        bytes,
+        len: len as u8,
+    }
+}
+
+impl FormattedFloat {
+    /// The printed number as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        debug_assert!(self.len as usize <= self.bytes.len());
+        unsafe { str::from_utf8_unchecked(self.bytes.get_unchecked(..self.len as usize)) }
+    }
+}
+
+impl Deref for FormattedFloat {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for FormattedFloat {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for FormattedFloat {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for FormattedFloat {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for FormattedFloat {}
+
+impl PartialEq<str> for FormattedFloat {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for FormattedFloat {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for FormattedFloat {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FormattedFloat {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for FormattedFloat {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Display for FormattedFloat {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for FormattedFloat {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), formatter)
+    }
+}
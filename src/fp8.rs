@@ -0,0 +1,286 @@
+// Formatting and parsing of the OCP 8-bit floating point formats, E4M3 (in
+// its "FN" variant: finite values and NaN, no infinities) and E5M2.
+//
+// Every FP8 value is exactly representable as an f32, so formatting reuses the
+// f32 shortest-digit search with the FP8 value's own, much wider, rounding
+// interval. Parsing compares the decimal input exactly against the midpoints
+// between adjacent FP8 values; the domain is small enough that a binary search
+// over all 128 magnitudes with 128-bit integer comparisons is cheap.
+
+use crate::f2s::f2d_parts;
+use crate::parse::Error;
+use crate::pretty::write_decimal32;
+use core::cmp::Ordering;
+use core::ptr;
+#[cfg(feature = "no-panic")]
+use no_panic::no_panic;
+
+const NAN: &str = "NaN";
+const INFINITY: &str = "inf";
+const NEG_INFINITY: &str = "-inf";
+
+const SIGN_MASK: u8 = 0x80;
+
+pub struct Format {
+    mantissa_bits: u32,
+    bias: i32,
+    // Largest finite magnitude. Finite inputs beyond it saturate to it.
+    max_finite: u8,
+    // Canonical NaN produced by the parser.
+    nan: u8,
+    infinity: Option<u8>,
+}
+
+pub const E4M3: Format = Format {
+    mantissa_bits: 3,
+    bias: 7,
+    max_finite: 0x7e,
+    nan: 0x7f,
+    infinity: None,
+};
+
+pub const E5M2: Format = Format {
+    mantissa_bits: 2,
+    bias: 15,
+    max_finite: 0x7b,
+    nan: 0x7e,
+    infinity: Some(0x7c),
+};
+
+impl Format {
+    #[inline]
+    pub fn is_nonfinite(&self, bits: u8) -> bool {
+        bits & !SIGN_MASK > self.max_finite
+    }
+
+    #[cold]
+    #[cfg_attr(feature = "no-panic", inline)]
+    pub fn format_nonfinite(&self, bits: u8) -> &'static str {
+        if Some(bits & !SIGN_MASK) != self.infinity {
+            NAN
+        } else if bits & SIGN_MASK != 0 {
+            NEG_INFINITY
+        } else {
+            INFINITY
+        }
+    }
+
+    // Decomposes a finite magnitude into (m, e, mm_shift) such that its value
+    // is m * 2^e, with mm_shift as computed by f2d.
+    #[cfg_attr(feature = "no-panic", inline)]
+    fn decode(&self, magnitude: u8) -> (u32, i32, u32) {
+        let ieee_mantissa = magnitude as u32 & ((1u32 << self.mantissa_bits) - 1);
+        let ieee_exponent = magnitude as u32 >> self.mantissa_bits;
+        let (m2, e2) = if ieee_exponent == 0 {
+            (ieee_mantissa, 1 - self.bias - self.mantissa_bits as i32)
+        } else {
+            (
+                (1u32 << self.mantissa_bits) | ieee_mantissa,
+                ieee_exponent as i32 - self.bias - self.mantissa_bits as i32,
+            )
+        };
+        let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u32;
+        (m2, e2, mm_shift)
+    }
+
+    // Print a finite FP8 value and return the number of bytes written, which
+    // is at most 16.
+    #[cfg_attr(feature = "no-panic", inline)]
+    pub unsafe fn format_finite(&self, bits: u8, result: *mut u8) -> usize {
+        debug_assert!(!self.is_nonfinite(bits));
+        let sign = bits & SIGN_MASK != 0;
+        let magnitude = bits & !SIGN_MASK;
+
+        let mut index = 0isize;
+        if sign {
+            *result = b'-';
+            index += 1;
+        }
+
+        if magnitude == 0 {
+            ptr::copy_nonoverlapping(b"0.0".as_ptr(), result.offset(index), 3);
+            return sign as usize + 3;
+        }
+
+        let (m2, e2, mm_shift) = self.decode(magnitude);
+        // We subtract 2 so that the bounds computation has 2 additional bits.
+        let v = f2d_parts(m2, e2 - 2, mm_shift);
+        index as usize + write_decimal32(v, result.offset(index))
+    }
+
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn parse(&self, buffer: &[u8]) -> Result<u8, Error> {
+        let (signed_m, magnitude) = match buffer {
+            b"NaN" => return Ok(self.nan),
+            b"inf" => (false, self.infinity.unwrap_or(self.max_finite)),
+            b"-inf" => (true, self.infinity.unwrap_or(self.max_finite)),
+            _ => {
+                let (signed_m, m10, m10digits, e10) = lex(buffer)?;
+                (signed_m, self.round(m10, m10digits, e10))
+            }
+        };
+        Ok(((signed_m as u8) << 7) | magnitude)
+    }
+
+    // Round m10 * 10^e10 to the nearest finite magnitude, ties to even.
+    #[cfg_attr(feature = "no-panic", inline)]
+    fn round(&self, m10: u64, m10digits: i32, e10: i32) -> u8 {
+        if m10 == 0 || m10digits + e10 <= -6 {
+            // Less than 1e-6, which is below half of the smallest subnormal in
+            // either format.
+            return 0;
+        }
+        if m10digits + e10 >= 6 {
+            // At least 1e5, which is beyond the largest finite value in either
+            // format.
+            return self.max_finite;
+        }
+
+        // Find the largest magnitude whose value is at most the input.
+        let mut lo = 0u8;
+        let mut hi = self.max_finite + 1;
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            let (m2, e2, _) = self.decode(mid);
+            if compare(m10, e10, m2, e2) == Ordering::Less {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        if lo == self.max_finite {
+            return lo;
+        }
+
+        // The next magnitude is exactly one unit in the last place of lo away,
+        // including across a binade boundary, so the midpoint between them is
+        // (2 * m2 + 1) * 2^(e2 - 1).
+        let (m2, e2, _) = self.decode(lo);
+        match compare(m10, e10, 2 * m2 + 1, e2 - 1) {
+            Ordering::Less => lo,
+            Ordering::Greater => lo + 1,
+            Ordering::Equal => lo + (lo & 1),
+        }
+    }
+}
+
+// Exactly compares m10 * 10^e10 against m2 * 2^e2. Requires -23 <= e10 <= 5
+// and -18 <= e2 <= 16 so that both sides fit in 128 bits.
+#[cfg_attr(feature = "no-panic", inline)]
+fn compare(m10: u64, e10: i32, m2: u32, e2: i32) -> Ordering {
+    debug_assert!(-23 <= e10 && e10 <= 5);
+    debug_assert!(-18 <= e2 && e2 <= 16);
+    let mut lhs = m10 as u128;
+    let mut rhs = m2 as u128;
+    if e10 >= 0 {
+        lhs *= 10u128.pow(e10 as u32);
+    } else {
+        rhs *= 10u128.pow(-e10 as u32);
+    }
+    if e2 >= 0 {
+        rhs <<= e2;
+    } else {
+        lhs <<= -e2;
+    }
+    lhs.cmp(&rhs)
+}
+
+// Splits a decimal number into its sign, mantissa, number of significant
+// mantissa digits, and exponent, accepting the same syntax as s2d.
+#[cfg_attr(feature = "no-panic", inline)]
+fn lex(buffer: &[u8]) -> Result<(bool, u64, i32, i32), Error> {
+    let len = buffer.len();
+    if len == 0 {
+        return Err(Error::InputTooShort);
+    }
+
+    let mut m10digits = 0;
+    let mut m10zeros = 0;
+    let mut e10digits = 0;
+    let mut dot_index = len;
+    let mut e_index = len;
+    let mut m10 = 0u64;
+    let mut e10 = 0i32;
+    let mut signed_m = false;
+    let mut signed_e = false;
+    let mut has_m10 = false;
+    let mut has_e10 = false;
+
+    let mut i = 0;
+    if buffer.first() == Some(&b'-') {
+        signed_m = true;
+        i += 1;
+    }
+
+    while let Some(c) = buffer.get(i).copied() {
+        if c == b'.' {
+            if dot_index != len {
+                return Err(Error::MalformedInput);
+            }
+            dot_index = i;
+            i += 1;
+            continue;
+        }
+        if c < b'0' || c > b'9' {
+            break;
+        }
+        if m10digits >= 17 {
+            if c != b'0' {
+                return Err(Error::InputTooLong);
+            }
+            // Zeros beyond the supported number of digits only scale the
+            // mantissa, so they are folded into the exponent instead.
+            m10zeros += 1;
+            i += 1;
+            continue;
+        }
+        m10 = 10 * m10 + (c - b'0') as u64;
+        if m10 != 0 {
+            m10digits += 1;
+        }
+        has_m10 = true;
+        i += 1;
+    }
+
+    if let Some(b'e' | b'E') = buffer.get(i) {
+        e_index = i;
+        i += 1;
+        match buffer.get(i) {
+            Some(b'-') => {
+                signed_e = true;
+                i += 1;
+            }
+            Some(b'+') => i += 1,
+            _ => {}
+        }
+        while let Some(c) = buffer.get(i).copied() {
+            if c < b'0' || c > b'9' {
+                return Err(Error::MalformedInput);
+            }
+            if e10digits > 3 {
+                return Err(Error::InputTooLong);
+            }
+            e10 = 10 * e10 + (c - b'0') as i32;
+            if e10 != 0 {
+                e10digits += 1;
+            }
+            has_e10 = true;
+            i += 1;
+        }
+    }
+
+    if i < len || !has_m10 || e_index < len && !has_e10 {
+        return Err(Error::MalformedInput);
+    }
+    if signed_e {
+        e10 = -e10;
+    }
+    e10 += m10zeros;
+    e10 -= if dot_index < e_index {
+        (e_index - dot_index - 1) as i32
+    } else {
+        0
+    };
+    Ok((signed_m, m10, m10digits, e10))
+}
@@ -0,0 +1,82 @@
+use crate::buffer::Float;
+use core::mem::MaybeUninit;
+use core::{slice, str};
+#[cfg(feature = "no-panic")]
+use no_panic::no_panic;
+
+/// Safe API for printing the exact value of a floating point number as a
+/// fraction.
+///
+/// Every finite float is a dyadic rational, m&nbsp;&times;&nbsp;2<sup>e</sup>.
+/// This prints it reduced to lowest terms as "numerator/denominator", for
+/// consumers such as computer algebra systems that need the exact value rather
+/// than the shortest decimal that rounds to it. The buffer is large enough for
+/// any f64, whose longest fractions have a numerator of 309 digits or a
+/// denominator of 324 digits.
+///
+/// ## Example
+///
+/// ```
+/// let mut buffer = ryu::FractionBuffer::new();
+/// assert_eq!(buffer.format(0.1f64), "3602879701896397/36028797018963968");
+/// assert_eq!(buffer.format(-0.75f32), "-3/4");
+/// assert_eq!(buffer.format(1e3f64), "1000/1");
+/// ```
+pub struct FractionBuffer {
+    bytes: [MaybeUninit<u8>; 342],
+}
+
+impl FractionBuffer {
+    /// This is a cheap operation; you don't need to worry about reusing buffers
+    /// for efficiency.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn new() -> Self {
+        let bytes = [MaybeUninit::<u8>::uninit(); 342];
+        FractionBuffer { bytes }
+    }
+
+    /// Print the exact value of a floating point number into this buffer and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// The denominator is always a power of 2, and is 1 for integers. Zero is
+    /// printed as "0/1", or "-0/1" if the sign bit is set.
+    ///
+    /// # Special cases
+    ///
+    /// NaN and the infinities have no exact value and are printed as "NaN",
+    /// "inf" and "-inf", like [`Buffer::format`].
+    ///
+    /// [`Buffer::format`]: crate::Buffer::format
+    #[cfg_attr(feature = "no-panic", inline)]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    pub fn format<F: Float>(&mut self, f: F) -> &str {
+        if f.is_nonfinite() {
+            return f.format_nonfinite();
+        }
+        unsafe {
+            let n = f.write_fraction(self.bytes.as_mut_ptr().cast::<u8>());
+            debug_assert!(n <= self.bytes.len());
+            let slice = slice::from_raw_parts(self.bytes.as_ptr().cast::<u8>(), n);
+            str::from_utf8_unchecked(slice)
+        }
+    }
+}
+
+impl Copy for FractionBuffer {}
+
+#[allow(clippy::non_canonical_clone_impl)]
+impl Clone for FractionBuffer {
+    #[inline]
+    fn clone(&self) -> Self {
+        FractionBuffer::new()
+    }
+}
+
+impl Default for FractionBuffer {
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic)]
+    fn default() -> Self {
+        FractionBuffer::new()
+    }
+}
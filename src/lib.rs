@@ -99,6 +99,7 @@
     clippy::doc_markdown,
     clippy::expl_impl_clone_on_copy,
     clippy::if_not_else,
+    clippy::manual_range_contains,
     clippy::many_single_char_names,
     clippy::missing_panics_doc,
     clippy::module_name_repetitions,
@@ -111,24 +112,253 @@
     clippy::wildcard_imports
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 mod buffer;
 mod common;
+mod const_format;
+#[cfg_attr(feature = "schubfach", allow(dead_code))]
 mod d2s;
-#[cfg(not(feature = "small"))]
+#[cfg(not(any(feature = "small", feature = "tiny")))]
 mod d2s_full_table;
 mod d2s_intrinsics;
-#[cfg(feature = "small")]
+#[cfg(all(feature = "small", not(feature = "tiny")))]
 mod d2s_small_table;
+mod decimal;
 mod digit_table;
+mod display;
+#[cfg_attr(feature = "schubfach", allow(dead_code))]
 mod f2s;
 mod f2s_intrinsics;
+mod formatted;
+mod fp8;
+mod fraction;
+mod parse;
 mod pretty;
+mod rational;
+mod s2d;
+mod s2f;
+#[cfg_attr(not(feature = "schubfach"), allow(dead_code))]
+mod schubfach;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod slice;
 #[cfg(test)]
 mod tests;
+mod write;
 
 pub use crate::buffer::{Buffer, Float};
+pub use crate::const_format::format_const;
+pub use crate::decimal::{formatted_len, Decimal};
+pub use crate::display::Display;
+pub use crate::formatted::{format_owned, FormattedFloat};
+pub use crate::fraction::FractionBuffer;
+pub use crate::parse::{Error as ParseError, Exactness, Rounding};
+pub use crate::rational::simplest_fraction;
+#[cfg(feature = "alloc")]
+pub use crate::slice::append_slice;
+#[cfg(feature = "std")]
+pub use crate::slice::format_slice_io;
+pub use crate::slice::{format_slice, NonFinite, SliceError};
+#[cfg(feature = "std")]
+pub use crate::write::write_io;
+#[cfg(feature = "alloc")]
+pub use crate::write::{append, append_string};
+pub use crate::write::{format_into, format_into_array, format_into_uninit, write_fmt};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::const_format::as_str;
+}
+
+/// Parse a floating point number from its decimal representation.
+///
+/// This accepts the output of [`Buffer::format`], including "NaN", "inf" and
+/// "-inf", as well as any decimal with an optional leading `-`, an optional
+/// fractional part, and an optional exponent introduced by `e` or `E`. There
+/// must be at least one digit before the exponent, and at least one after the
+/// `e`, so "." and "1e" are malformed. The mantissa may have at most 17
+/// significant digits for f64, or 9 for f32, which is enough to read back any
+/// number printed by [`Buffer::format`]. Zeros after the last significant
+/// digit do not count toward that limit. The result is correctly rounded,
+/// ties to even.
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(ryu::parse::<f64>(b"1.234"), Ok(1.234));
+/// assert_eq!(ryu::parse::<f32>(b"-1e-45"), Ok(-1e-45));
+/// assert_eq!(ryu::parse::<f64>(b"-inf"), Ok(f64::NEG_INFINITY));
+/// assert!(ryu::parse::<f64>(b"NaN").unwrap().is_nan());
+/// assert!(ryu::parse::<f64>(b"1.2.3").is_err());
+/// ```
+///
+/// ## Errors
+///
+/// Returns [`ParseError::InputTooShort`] for empty input,
+/// [`ParseError::InputTooLong`] for more significant digits than the limit
+/// above or an exponent too long to represent, and
+/// [`ParseError::MalformedInput`] for anything else that is not a decimal
+/// number.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn parse<F: Float>(bytes: &[u8]) -> Result<F, ParseError> {
+    F::parse_bytes(bytes)
+}
+
+/// Parse a floating point number from its decimal representation, rounding
+/// in the given direction.
+///
+/// This accepts the same input as [`parse`], except that a directed rounding
+/// mode allows 18 significant digits for f64, enough to read back the output
+/// of [`Buffer::format_rounded`]. Rounding with
+/// [`Rounding::Downward`] gives the largest float not greater than the
+/// decimal, and [`Rounding::Upward`] the smallest float not less than it, for
+/// example for the two bounds of an interval. A directed rounding mode skips
+/// the fast paths of `parse`, so this is somewhat slower unless rounding to
+/// nearest.
+///
+/// ## Example
+///
+/// ```
+/// use ryu::Rounding;
+///
+/// let lower = ryu::parse_rounded::<f64>(b"0.1", Rounding::Downward).unwrap();
+/// let upper = ryu::parse_rounded::<f64>(b"0.1", Rounding::Upward).unwrap();
+/// assert_eq!(lower, 0.09999999999999999);
+/// assert_eq!(upper, 0.1);
+///
+/// let max = ryu::parse_rounded::<f32>(b"-1e39", Rounding::TowardZero).unwrap();
+/// assert_eq!(max, f32::MIN);
+/// ```
+///
+/// ## Errors
+///
+/// Returns [`ParseError::InputTooShort`] for empty input,
+/// [`ParseError::InputTooLong`] for more significant digits than described
+/// above or an exponent too long to represent, and
+/// [`ParseError::MalformedInput`] for anything else that is not a decimal
+/// number.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn parse_rounded<F: Float>(bytes: &[u8], rounding: Rounding) -> Result<F, ParseError> {
+    match rounding {
+        Rounding::NearestEven => F::parse_bytes(bytes),
+        _ => F::parse_bytes_exactness(bytes, rounding).map(|(f, _exactness)| f),
+    }
+}
+
+/// Parse a floating point number from its decimal representation, and report
+/// whether it was exact.
+///
+/// This accepts the same input as [`parse`] and returns the same number,
+/// together with an [`Exactness`] telling whether the decimal was exactly
+/// representable, which way it was rounded if not, and whether it overflowed
+/// to infinity or underflowed to a subnormal number or zero. Computing the
+/// exactness skips the fast paths of `parse`, so this is somewhat slower.
+///
+/// ## Example
+///
+/// ```
+/// use ryu::Exactness;
+///
+/// assert_eq!(ryu::parse_with_exactness::<f64>(b"0.5"), Ok((0.5, Exactness::Exact)));
+/// assert_eq!(ryu::parse_with_exactness::<f64>(b"0.1"), Ok((0.1, Exactness::RoundedUp)));
+/// assert_eq!(ryu::parse_with_exactness::<f64>(b"-0.1"), Ok((-0.1, Exactness::RoundedDown)));
+/// assert_eq!(ryu::parse_with_exactness::<f32>(b"1e39"), Ok((f32::INFINITY, Exactness::Overflow)));
+/// assert_eq!(ryu::parse_with_exactness::<f32>(b"1e-46"), Ok((0.0, Exactness::Underflow)));
+/// ```
+///
+/// ## Errors
+///
+/// Returns the same errors as [`parse`]: [`ParseError::InputTooShort`] for
+/// empty input, [`ParseError::InputTooLong`] for too many significant digits
+/// or an exponent too long to represent, and [`ParseError::MalformedInput`]
+/// for anything else that is not a decimal number.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn parse_with_exactness<F: Float>(bytes: &[u8]) -> Result<(F, Exactness), ParseError> {
+    F::parse_bytes_exactness(bytes, Rounding::NearestEven)
+}
+
+/// Construct a floating point number from a decimal mantissa and exponent.
+///
+/// Returns `mantissa * 10^exponent`, negated if `negative` is set, correctly
+/// rounded to the nearest f32 or f64, ties to even. This is the conversion
+/// [`parse`] performs after reading the digits, for callers that already have
+/// the decimal pieces, such as a scaled integer. Unlike `parse`, the mantissa
+/// may use all 20 digits of a u64, and the exponent is not limited; values out
+/// of range become &plusmn;0 or &plusmn;infinity.
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(ryu::from_decimal::<f64>(1234, -3, false), 1.234);
+/// assert_eq!(ryu::from_decimal::<f32>(5, -1, true), -0.5);
+/// assert_eq!(ryu::from_decimal::<f64>(u64::MAX, 0, false), 18446744073709551615.0);
+/// assert_eq!(ryu::from_decimal::<f64>(1, 400, false), f64::INFINITY);
+/// ```
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn from_decimal<F: Float>(mantissa: u64, exponent: i32, negative: bool) -> F {
+    F::from_decimal(mantissa, exponent, negative)
+}
+
+/// Parse an [OCP FP8] E4M3 value from its decimal representation and return
+/// its bit pattern.
+///
+/// This accepts the output of [`Buffer::format_e4m3`] as well as any decimal
+/// with at most 17 significant digits, such as `"0.1"` or `"-1.5e2"`. The
+/// result is rounded to the nearest E4M3 value, ties to even. E4M3 has no
+/// infinities, so finite values beyond &plusmn;448 as well as `"inf"` and
+/// `"-inf"` saturate to &plusmn;448. `"NaN"` parses as `0x7f`.
+///
+/// [OCP FP8]: https://www.opencompute.org/documents/ocp-8-bit-floating-point-specification-ofp8-revision-1-0-2023-12-01-pdf-1
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(ryu::parse_e4m3(b"0.1"), Ok(0x1d));
+/// assert_eq!(ryu::parse_e4m3(b"1000"), Ok(0x7e));
+/// ```
+///
+/// ## Errors
+///
+/// Returns [`ParseError::InputTooShort`] for empty input,
+/// [`ParseError::InputTooLong`] for more than 17 significant digits or an
+/// exponent too long to represent, and [`ParseError::MalformedInput`] for
+/// anything else that is not a decimal number.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn parse_e4m3(bytes: &[u8]) -> Result<u8, ParseError> {
+    fp8::E4M3.parse(bytes)
+}
+
+/// Parse an [OCP FP8] E5M2 value from its decimal representation and return
+/// its bit pattern.
+///
+/// This accepts the output of [`Buffer::format_e5m2`] as well as any decimal
+/// with at most 17 significant digits. The result is rounded to the nearest
+/// E5M2 value, ties to even. Finite values beyond &plusmn;57344 saturate to
+/// &plusmn;57344, while `"inf"` and `"-inf"` parse as the E5M2 infinities.
+/// `"NaN"` parses as `0x7e`.
+///
+/// [OCP FP8]: https://www.opencompute.org/documents/ocp-8-bit-floating-point-specification-ofp8-revision-1-0-2023-12-01-pdf-1
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(ryu::parse_e5m2(b"0.1"), Ok(0x2e));
+/// assert_eq!(ryu::parse_e5m2(b"inf"), Ok(0x7c));
+/// ```
+///
+/// ## Errors
+///
+/// The same as [`parse_e4m3`].
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn parse_e5m2(bytes: &[u8]) -> Result<u8, ParseError> {
+    fp8::E5M2.parse(bytes)
+}
 
 /// Unsafe functions that mirror the API of the C implementation of Ryū.
 pub mod raw {
-    pub use crate::pretty::{format32, format64};
+    pub use crate::pretty::{format32, format32_fraction, format64, format64_fraction};
 }
@@ -1,12 +1,96 @@
 use core::fmt::{self, Display};
+use core::ptr;
 
-#[derive(Copy, Clone, Debug)]
+/// Error returned when parsing a decimal string fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Error {
+    /// The input was empty.
     InputTooShort,
+    /// The input had more significant digits, or a longer exponent, than
+    /// the parser supports.
     InputTooLong,
+    /// The input was not a decimal number.
     MalformedInput,
 }
 
+/// How a parsed float relates to the exact value of the decimal it was parsed
+/// from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Exactness {
+    /// The float is exactly the decimal.
+    Exact,
+    /// The decimal was not representable, and the float is the next one above
+    /// it.
+    RoundedUp,
+    /// The decimal was not representable, and the float is the next one below
+    /// it.
+    RoundedDown,
+    /// The decimal was beyond the largest finite float, so the result is
+    /// infinite or the largest finite float, depending on rounding.
+    Overflow,
+    /// The decimal was not representable and too small for a normal float, and
+    /// the result is subnormal or zero.
+    Underflow,
+}
+
+/// How to round a decimal that is not exactly representable when parsing it,
+/// or which side of a float to pick the printed decimal from when formatting it
+/// with [`Buffer::format_rounded`].
+///
+/// When formatting, `Upward` prints the shortest decimal that is not less than
+/// the float and `Downward` the shortest that is not greater, so that reading
+/// the output as an exact decimal gives a bound on the float's value.
+///
+/// [`Buffer::format_rounded`]: crate::Buffer::format_rounded
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// To the nearest float, and between two equally near, the one with an
+    /// even mantissa.
+    #[default]
+    NearestEven,
+    /// Like `Downward` for positive decimals and `Upward` for negative ones.
+    TowardZero,
+    /// To the smallest float not less than the decimal.
+    Upward,
+    /// To the largest float not greater than the decimal.
+    Downward,
+}
+
+impl Rounding {
+    // Whether an inexact result of the given sign has its magnitude rounded
+    // down, whichever float is nearest.
+    pub fn toward_zero(self, signed: bool) -> bool {
+        match self {
+            Rounding::NearestEven => false,
+            Rounding::TowardZero => true,
+            Rounding::Upward => signed,
+            Rounding::Downward => !signed,
+        }
+    }
+
+    // Whether an inexact result of the given sign has its magnitude rounded
+    // up, whichever float is nearest.
+    pub fn away_from_zero(self, signed: bool) -> bool {
+        match self {
+            Rounding::NearestEven | Rounding::TowardZero => false,
+            Rounding::Upward => !signed,
+            Rounding::Downward => signed,
+        }
+    }
+}
+
+impl Exactness {
+    // The exactness of a result whose magnitude was rounded up or down from
+    // the decimal, given the sign.
+    pub fn rounded(round_up: bool, signed: bool) -> Self {
+        if round_up == signed {
+            Exactness::RoundedDown
+        } else {
+            Exactness::RoundedUp
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         let msg = match self {
@@ -17,3 +101,37 @@ impl Display for Error {
         formatter.write_str(msg)
     }
 }
+
+// Returns the value of the 8 ASCII digits at buffer[i..i + 8], or None if
+// fewer than 8 bytes remain or any of them is not a digit.
+//
+// The bytes are loaded as one little-endian u64 and checked and converted with
+// SWAR arithmetic (SIMD within a register): first digit pairs in 16-bit lanes,
+// then all four pairs at once with two multiplications, in place of eight
+// rounds of multiply-by-10.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn parse_eight_digits(buffer: &[u8], i: usize) -> Option<u32> {
+    let chunk = buffer.get(i..i + 8)?;
+    let v = u64::from_le(unsafe { ptr::read_unaligned(chunk.as_ptr().cast::<u64>()) });
+    // A byte is a digit if adding 0x46 does not carry into its high bit and
+    // subtracting 0x30 does not borrow from it.
+    if (v.wrapping_add(0x4646_4646_4646_4646) | v.wrapping_sub(0x3030_3030_3030_3030))
+        & 0x8080_8080_8080_8080
+        != 0
+    {
+        return None;
+    }
+    // Eight lanes of 8 bits, each below 10, the first digit in the low lane.
+    let v = v - 0x3030_3030_3030_3030;
+    // Every other lane of 8 bits now holds a pair of digits, below 10^2.
+    let v = v * 10 + (v >> 8);
+    // Combine the four pairs, scaled by 10^6, 10^4, 10^2 and 1, in the upper
+    // 32 bits of two products.
+    let mask = 0x0000_00ff_0000_00ff;
+    let mul1 = 100 + (1_000_000 << 32);
+    let mul2 = 1 + (10_000 << 32);
+    let v = (v & mask)
+        .wrapping_mul(mul1)
+        .wrapping_add(((v >> 16) & mask).wrapping_mul(mul2));
+    Some((v >> 32) as u32)
+}
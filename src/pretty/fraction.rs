@@ -0,0 +1,75 @@
+use super::mantissa::{write_mantissa, write_mantissa8};
+use crate::common;
+
+// Enough base 10^9 limbs for 2^1074, the largest power of 2 that appears in
+// the value of an f64, which has 324 decimal digits.
+const LIMBS: usize = 36;
+
+// Writes m * 2^e, which must be nonzero, as "numerator/denominator" in lowest
+// terms. Returns the number of bytes written.
+#[cfg_attr(feature = "no-panic", inline)]
+pub unsafe fn write_fraction(m: u64, e: i32, result: *mut u8) -> usize {
+    debug_assert!(m != 0);
+    let (numerator, numerator_shift, denominator_shift) = if e >= 0 {
+        (m, e as u32, 0)
+    } else {
+        // The denominator has only factors of 2, so cancel the ones that the
+        // numerator has too.
+        let shift = m.trailing_zeros().min(-e as u32);
+        (m >> shift, 0, -e as u32 - shift)
+    };
+    let mut index = write_big(numerator, numerator_shift, result);
+    *result.add(index) = b'/';
+    index += 1;
+    index + write_big(1, denominator_shift, result.add(index))
+}
+
+// Writes m * 2^shift in decimal and returns the number of digits.
+#[cfg_attr(feature = "no-panic", inline)]
+unsafe fn write_big(m: u64, mut shift: u32, result: *mut u8) -> usize {
+    // Little-endian digits in base 10^9.
+    let mut limbs = [0u32; LIMBS];
+    limbs[0] = (m % 1_000_000_000) as u32;
+    limbs[1] = (m / 1_000_000_000 % 1_000_000_000) as u32;
+    limbs[2] = (m / 1_000_000_000_000_000_000) as u32;
+    let mut len = if limbs[2] != 0 {
+        3
+    } else if limbs[1] != 0 {
+        2
+    } else {
+        1
+    };
+
+    // Each limb is below 2^30, so doubling it 29 times plus a carry below
+    // 2^29 still fits in u64.
+    while shift != 0 {
+        let step = shift.min(29);
+        shift -= step;
+        let mut carry = 0u64;
+        for i in 0..len {
+            let limb = limbs.get_unchecked_mut(i);
+            let v = ((*limb as u64) << step) + carry;
+            *limb = (v % 1_000_000_000) as u32;
+            carry = v / 1_000_000_000;
+        }
+        if carry != 0 {
+            debug_assert!(len < LIMBS);
+            *limbs.get_unchecked_mut(len) = carry as u32;
+            len += 1;
+        }
+    }
+
+    // The most significant limb without leading zeros, then 9 digits for each
+    // of the others.
+    let top = *limbs.get_unchecked(len - 1);
+    let top_length = common::decimal_length9(top) as usize;
+    let mut end = result.add(top_length);
+    write_mantissa(top, end);
+    for i in (0..len - 1).rev() {
+        let limb = *limbs.get_unchecked(i);
+        *end = b'0' + (limb / 100_000_000) as u8;
+        write_mantissa8(limb % 100_000_000, end.add(9));
+        end = end.add(9);
+    }
+    top_length + 9 * (len - 1)
+}
@@ -1,18 +1,35 @@
 mod exponent;
+mod fraction;
 mod mantissa;
 
 use self::exponent::{write_exponent2, write_exponent3};
+use self::fraction::write_fraction;
 use self::mantissa::{write_mantissa, write_mantissa_long};
 use crate::common;
-use crate::d2s::{self, d2d, DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS};
-use crate::f2s::{f2d, FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+#[cfg(not(feature = "schubfach"))]
+use crate::d2s::d2d;
+use crate::d2s::{
+    self, FloatingDecimal64, DOUBLE_BIAS, DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS,
+};
+#[cfg(not(feature = "schubfach"))]
+use crate::f2s::f2d;
+use crate::f2s::{FloatingDecimal32, FLOAT_BIAS, FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+#[cfg(feature = "schubfach")]
+use crate::schubfach::{d2d, f2d};
 use core::ptr;
 #[cfg(feature = "no-panic")]
 use no_panic::no_panic;
 
 /// Print f64 to the given buffer and return number of bytes written.
 ///
-/// At most 24 bytes will be written.
+/// At most 24 bytes will be written. This bound is for the shortest
+/// representation only: a [`Decimal`] from [`Decimal::new_rounded`] can have an
+/// 18-digit mantissa and take 25 bytes, which is why [`Buffer`] is one byte
+/// larger than this.
+///
+/// [`Decimal`]: crate::Decimal
+/// [`Decimal::new_rounded`]: crate::Decimal::new_rounded
+/// [`Buffer`]: crate::Buffer
 ///
 /// ## Special cases
 ///
@@ -67,53 +84,190 @@ pub unsafe fn format64(f: f64, result: *mut u8) -> usize {
         return sign as usize + 3;
     }
 
+    // An integer below 2^53 is the shortest decimal in its own rounding
+    // interval, which is at most 1 wide, so it prints as its digits and ".0"
+    // without needing d2d.
+    let e = ieee_exponent as i32 - DOUBLE_BIAS;
+    if 0 <= e && e < DOUBLE_MANTISSA_BITS as i32 + 1 {
+        let fraction_bits = DOUBLE_MANTISSA_BITS - e as u32;
+        if ieee_mantissa & ((1u64 << fraction_bits) - 1) == 0 {
+            let n = ((1u64 << DOUBLE_MANTISSA_BITS) | ieee_mantissa) >> fraction_bits;
+            let length = d2s::decimal_length17(n) as isize;
+            let end = result.offset(index + length);
+            write_mantissa_long(n, end);
+            *end = b'.';
+            *end.add(1) = b'0';
+            return index as usize + length as usize + 2;
+        }
+    }
+
     let v = d2d(ieee_mantissa, ieee_exponent);
+    index as usize + write_decimal64(v, result.offset(index))
+}
+
+/// Print the exact value of an f64 as a fraction in lowest terms to the given
+/// buffer and return number of bytes written.
+///
+/// The output is a numerator with an optional leading `-`, a `/`, and a
+/// denominator that is a power of 2, such as "3602879701896397/36028797018963968"
+/// for 0.1. Integers have a denominator of 1. Zero is printed as "0/1", or
+/// "-0/1" if the sign bit is set.
+///
+/// At most 342 bytes will be written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the fraction representation of `f`.
+///
+/// ## Example
+///
+/// ```
+/// use std::{mem::MaybeUninit, slice, str};
+///
+/// let f = 0.1f64;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 342];
+///     let len = ryu::raw::format64_fraction(f, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "3602879701896397/36028797018963968");
+/// }
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic)]
+pub unsafe fn format64_fraction(f: f64, result: *mut u8) -> usize {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
 
-    let length = d2s::decimal_length17(v.mantissa) as isize;
+    let mut index = 0;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0/1".as_ptr(), result.add(index), 3);
+        return index + 3;
+    }
+
+    let (e2, m2) = if ieee_exponent == 0 {
+        (1 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32, ieee_mantissa)
+    } else {
+        (
+            ieee_exponent as i32 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32,
+            (1u64 << DOUBLE_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    index + write_fraction(m2, e2, result.add(index))
+}
+
+// Lays out the unsigned shortest decimal of an f64 using the same rules as
+// format64. Returns the number of bytes written, which is at most 23, or 24
+// for the 18-digit mantissas that d2d_directed can produce. Those are below
+// about 1.8 * 10^17, so write_mantissa_long still needs only one 64-bit
+// division.
+#[cfg_attr(feature = "no-panic", inline)]
+pub unsafe fn write_decimal64(v: FloatingDecimal64, result: *mut u8) -> usize {
+    debug_assert!(v.mantissa / 100_000_000 <= u32::MAX as u64);
+    let length = d2s::decimal_length18(v.mantissa) as isize;
     let k = v.exponent as isize;
     let kk = length + k; // 10^(kk-1) <= v < 10^kk
-    debug_assert!(k >= -324);
+
+    // One below d2d's smallest exponent, for d2d_directed on subnormals.
+    debug_assert!(k >= -325);
 
     if 0 <= k && kk <= 16 {
         // 1234e7 -> 12340000000.0
-        write_mantissa_long(v.mantissa, result.offset(index + length));
+        write_mantissa_long(v.mantissa, result.offset(length));
         for i in length..kk {
-            *result.offset(index + i) = b'0';
+            *result.offset(i) = b'0';
         }
-        *result.offset(index + kk) = b'.';
-        *result.offset(index + kk + 1) = b'0';
-        index as usize + kk as usize + 2
+        *result.offset(kk) = b'.';
+        *result.offset(kk + 1) = b'0';
+        kk as usize + 2
     } else if 0 < kk && kk <= 16 {
         // 1234e-2 -> 12.34
-        write_mantissa_long(v.mantissa, result.offset(index + length + 1));
-        ptr::copy(result.offset(index + 1), result.offset(index), kk as usize);
-        *result.offset(index + kk) = b'.';
-        index as usize + length as usize + 1
+        write_mantissa_long(v.mantissa, result.offset(length + 1));
+        ptr::copy(result.add(1), result, kk as usize);
+        *result.offset(kk) = b'.';
+        length as usize + 1
     } else if -5 < kk && kk <= 0 {
         // 1234e-6 -> 0.001234
-        *result.offset(index) = b'0';
-        *result.offset(index + 1) = b'.';
+        *result = b'0';
+        *result.add(1) = b'.';
         let offset = 2 - kk;
         for i in 2..offset {
-            *result.offset(index + i) = b'0';
+            *result.offset(i) = b'0';
         }
-        write_mantissa_long(v.mantissa, result.offset(index + length + offset));
-        index as usize + length as usize + offset as usize
+        write_mantissa_long(v.mantissa, result.offset(length + offset));
+        length as usize + offset as usize
     } else if length == 1 {
         // 1e30
-        *result.offset(index) = b'0' + v.mantissa as u8;
-        *result.offset(index + 1) = b'e';
-        index as usize + 2 + write_exponent3(kk - 1, result.offset(index + 2))
+        *result = b'0' + v.mantissa as u8;
+        *result.add(1) = b'e';
+        2 + write_exponent3(kk - 1, result.add(2))
     } else {
         // 1234e30 -> 1.234e33
-        write_mantissa_long(v.mantissa, result.offset(index + length + 1));
-        *result.offset(index) = *result.offset(index + 1);
-        *result.offset(index + 1) = b'.';
-        *result.offset(index + length + 1) = b'e';
-        index as usize
-            + length as usize
-            + 2
-            + write_exponent3(kk - 1, result.offset(index + length + 2))
+        write_mantissa_long(v.mantissa, result.offset(length + 1));
+        *result = *result.add(1);
+        *result.add(1) = b'.';
+        *result.offset(length + 1) = b'e';
+        length as usize + 2 + write_exponent3(kk - 1, result.offset(length + 2))
+    }
+}
+
+// Number of bytes that write_decimal64 would write for v.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decimal_len64(v: FloatingDecimal64) -> usize {
+    let length = d2s::decimal_length18(v.mantissa) as isize;
+    let kk = length + v.exponent as isize;
+    decimal_len(length, kk, 16, -5)
+}
+
+// Number of bytes that write_decimal32 would write for v.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decimal_len32(v: FloatingDecimal32) -> usize {
+    let length = common::decimal_length9(v.mantissa) as isize;
+    let kk = length + v.exponent as isize;
+    decimal_len(length, kk, 13, -6)
+}
+
+// Mirrors the branches of write_decimal64 and write_decimal32, which differ
+// only in the thresholds for switching to scientific notation.
+#[cfg_attr(feature = "no-panic", inline)]
+fn decimal_len(length: isize, kk: isize, max_kk: isize, min_kk: isize) -> usize {
+    let k = kk - length;
+    if 0 <= k && kk <= max_kk {
+        // 1234e7 -> 12340000000.0
+        kk as usize + 2
+    } else if 0 < kk && kk <= max_kk {
+        // 1234e-2 -> 12.34
+        length as usize + 1
+    } else if min_kk < kk && kk <= 0 {
+        // 1234e-6 -> 0.001234
+        (length + 2 - kk) as usize
+    } else {
+        // 1e30, 1234e30 -> 1.234e33
+        let e = kk - 1;
+        let exponent_len = (e < 0) as usize
+            + match e.unsigned_abs() {
+                0..=9 => 1,
+                10..=99 => 2,
+                _ => 3,
+            };
+        let mantissa_len = if length == 1 { 1 } else { length as usize + 1 };
+        mantissa_len + 1 + exponent_len
     }
 }
 
@@ -173,52 +327,137 @@ pub unsafe fn format32(f: f32, result: *mut u8) -> usize {
         return sign as usize + 3;
     }
 
+    // Integers below 2^24, the same as in format64.
+    let e = ieee_exponent as i32 - FLOAT_BIAS;
+    if 0 <= e && e < FLOAT_MANTISSA_BITS as i32 + 1 {
+        let fraction_bits = FLOAT_MANTISSA_BITS - e as u32;
+        if ieee_mantissa & ((1u32 << fraction_bits) - 1) == 0 {
+            let n = ((1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa) >> fraction_bits;
+            let length = common::decimal_length9(n) as isize;
+            let end = result.offset(index + length);
+            write_mantissa(n, end);
+            *end = b'.';
+            *end.add(1) = b'0';
+            return index as usize + length as usize + 2;
+        }
+    }
+
     let v = f2d(ieee_mantissa, ieee_exponent);
+    index as usize + write_decimal32(v, result.offset(index))
+}
+
+/// Print the exact value of an f32 as a fraction in lowest terms to the given
+/// buffer and return number of bytes written.
+///
+/// The output has the same form as [`format64_fraction`], such as
+/// "13421773/134217728" for 0.1.
+///
+/// At most 55 bytes will be written.
+///
+/// ## Special cases
+///
+/// This function **does not** check for NaN or infinity. If the input
+/// number is not a finite float, the printed representation will be some
+/// correctly formatted but unspecified numerical value.
+///
+/// ## Safety
+///
+/// The `result` pointer argument must point to sufficiently many writable bytes
+/// to hold the fraction representation of `f`.
+///
+/// ## Example
+///
+/// ```
+/// use std::{mem::MaybeUninit, slice, str};
+///
+/// let f = -1.5f32;
+///
+/// unsafe {
+///     let mut buffer = [MaybeUninit::<u8>::uninit(); 55];
+///     let len = ryu::raw::format32_fraction(f, buffer.as_mut_ptr() as *mut u8);
+///     let slice = slice::from_raw_parts(buffer.as_ptr() as *const u8, len);
+///     let print = str::from_utf8_unchecked(slice);
+///     assert_eq!(print, "-3/2");
+/// }
+/// ```
+#[must_use]
+#[cfg_attr(feature = "no-panic", no_panic)]
+pub unsafe fn format32_fraction(f: f32, result: *mut u8) -> usize {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
 
+    let mut index = 0;
+    if sign {
+        *result = b'-';
+        index += 1;
+    }
+
+    if ieee_exponent == 0 && ieee_mantissa == 0 {
+        ptr::copy_nonoverlapping(b"0/1".as_ptr(), result.add(index), 3);
+        return index + 3;
+    }
+
+    let (e2, m2) = if ieee_exponent == 0 {
+        (1 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32, ieee_mantissa)
+    } else {
+        (
+            ieee_exponent as i32 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32,
+            (1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    index + write_fraction(m2 as u64, e2, result.add(index))
+}
+
+// Lays out the unsigned shortest decimal of an f32 (or of a narrower format
+// whose digits come from f2d_parts) using the same rules as format32. Returns
+// the number of bytes written, which is at most 15.
+#[cfg_attr(feature = "no-panic", inline)]
+pub unsafe fn write_decimal32(v: FloatingDecimal32, result: *mut u8) -> usize {
     let length = common::decimal_length9(v.mantissa) as isize;
     let k = v.exponent as isize;
     let kk = length + k; // 10^(kk-1) <= v < 10^kk
-    debug_assert!(k >= -45);
+
+    // One below f2d's smallest exponent, for f2d_directed on subnormals.
+    debug_assert!(k >= -46);
 
     if 0 <= k && kk <= 13 {
         // 1234e7 -> 12340000000.0
-        write_mantissa(v.mantissa, result.offset(index + length));
+        write_mantissa(v.mantissa, result.offset(length));
         for i in length..kk {
-            *result.offset(index + i) = b'0';
+            *result.offset(i) = b'0';
         }
-        *result.offset(index + kk) = b'.';
-        *result.offset(index + kk + 1) = b'0';
-        index as usize + kk as usize + 2
+        *result.offset(kk) = b'.';
+        *result.offset(kk + 1) = b'0';
+        kk as usize + 2
     } else if 0 < kk && kk <= 13 {
         // 1234e-2 -> 12.34
-        write_mantissa(v.mantissa, result.offset(index + length + 1));
-        ptr::copy(result.offset(index + 1), result.offset(index), kk as usize);
-        *result.offset(index + kk) = b'.';
-        index as usize + length as usize + 1
+        write_mantissa(v.mantissa, result.offset(length + 1));
+        ptr::copy(result.add(1), result, kk as usize);
+        *result.offset(kk) = b'.';
+        length as usize + 1
     } else if -6 < kk && kk <= 0 {
         // 1234e-6 -> 0.001234
-        *result.offset(index) = b'0';
-        *result.offset(index + 1) = b'.';
+        *result = b'0';
+        *result.add(1) = b'.';
         let offset = 2 - kk;
         for i in 2..offset {
-            *result.offset(index + i) = b'0';
+            *result.offset(i) = b'0';
         }
-        write_mantissa(v.mantissa, result.offset(index + length + offset));
-        index as usize + length as usize + offset as usize
+        write_mantissa(v.mantissa, result.offset(length + offset));
+        length as usize + offset as usize
     } else if length == 1 {
         // 1e30
-        *result.offset(index) = b'0' + v.mantissa as u8;
-        *result.offset(index + 1) = b'e';
-        index as usize + 2 + write_exponent2(kk - 1, result.offset(index + 2))
+        *result = b'0' + v.mantissa as u8;
+        *result.add(1) = b'e';
+        2 + write_exponent2(kk - 1, result.add(2))
     } else {
         // 1234e30 -> 1.234e33
-        write_mantissa(v.mantissa, result.offset(index + length + 1));
-        *result.offset(index) = *result.offset(index + 1);
-        *result.offset(index + 1) = b'.';
-        *result.offset(index + length + 1) = b'e';
-        index as usize
-            + length as usize
-            + 2
-            + write_exponent2(kk - 1, result.offset(index + length + 2))
+        write_mantissa(v.mantissa, result.offset(length + 1));
+        *result = *result.add(1);
+        *result.add(1) = b'.';
+        *result.offset(length + 1) = b'e';
+        length as usize + 2 + write_exponent2(kk - 1, result.offset(length + 2))
     }
 }
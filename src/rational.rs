@@ -0,0 +1,150 @@
+use crate::buffer::Float;
+use crate::d2s::{DOUBLE_BIAS, DOUBLE_EXPONENT_BITS, DOUBLE_MANTISSA_BITS};
+use crate::f2s::{FLOAT_BIAS, FLOAT_EXPONENT_BITS, FLOAT_MANTISSA_BITS};
+
+/// Find the fraction with the smallest denominator among all the real numbers
+/// that round to the given floating point number.
+///
+/// This is the same interval that [`Buffer::format`] picks its shortest
+/// decimal from, searched for the simplest fraction instead, so that a float
+/// computed as `1.0 / 3.0` is recovered as 1/3. The result is returned as a
+/// numerator carrying the sign and a positive denominator, in lowest terms.
+/// An integer is returned as itself over 1, and zero as 0/1.
+///
+/// Returns `None` for NaN and the infinities, and when the numerator does not
+/// fit in i64 or the denominator does not fit in u64, which is the case for
+/// integers of magnitude 2<sup>63</sup> or more and for numbers very close to
+/// zero.
+///
+/// [`Buffer::format`]: crate::Buffer::format
+///
+/// ## Example
+///
+/// ```
+/// assert_eq!(ryu::simplest_fraction(0.3333333333333333f64), Some((1, 3)));
+/// assert_eq!(ryu::simplest_fraction(0.1f64), Some((1, 10)));
+/// assert_eq!(ryu::simplest_fraction(-2.5f32), Some((-5, 2)));
+/// assert_eq!(ryu::simplest_fraction(core::f64::consts::PI), Some((245850922, 78256779)));
+/// assert_eq!(ryu::simplest_fraction(1e-300f64), None);
+/// ```
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn simplest_fraction<F: Float>(f: F) -> Option<(i64, u64)> {
+    f.simplest_fraction()
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn simplest_fraction64(f: f64) -> Option<(i64, u64)> {
+    let bits = f.to_bits();
+    let sign = ((bits >> (DOUBLE_MANTISSA_BITS + DOUBLE_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u64 << DOUBLE_MANTISSA_BITS) - 1);
+    let ieee_exponent =
+        (bits >> DOUBLE_MANTISSA_BITS) as u32 & ((1u32 << DOUBLE_EXPONENT_BITS) - 1);
+    if ieee_exponent == (1u32 << DOUBLE_EXPONENT_BITS) - 1 {
+        return None;
+    }
+    let (e2, m2) = if ieee_exponent == 0 {
+        (1 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32, ieee_mantissa)
+    } else {
+        (
+            ieee_exponent as i32 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32,
+            (1u64 << DOUBLE_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    simplest(sign, m2, e2, mm_shift)
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn simplest_fraction32(f: f32) -> Option<(i64, u64)> {
+    let bits = f.to_bits();
+    let sign = ((bits >> (FLOAT_MANTISSA_BITS + FLOAT_EXPONENT_BITS)) & 1) != 0;
+    let ieee_mantissa = bits & ((1u32 << FLOAT_MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> FLOAT_MANTISSA_BITS) & ((1u32 << FLOAT_EXPONENT_BITS) - 1);
+    if ieee_exponent == (1u32 << FLOAT_EXPONENT_BITS) - 1 {
+        return None;
+    }
+    let (e2, m2) = if ieee_exponent == 0 {
+        (1 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32, ieee_mantissa)
+    } else {
+        (
+            ieee_exponent as i32 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32,
+            (1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+    simplest(sign, m2 as u64, e2, mm_shift)
+}
+
+// Simplest fraction in the rounding interval of m2 * 2^e2, where m2 has at most
+// 53 bits.
+#[cfg_attr(feature = "no-panic", inline)]
+fn simplest(sign: bool, m2: u64, e2: i32, mm_shift: u64) -> Option<(i64, u64)> {
+    if m2 == 0 {
+        return Some((0, 1));
+    }
+
+    // Integers have their own value as the simplest fraction. Other integers
+    // may share the interval once the spacing of floats exceeds 1, but they
+    // are no simpler.
+    let (numerator, denominator) = if e2 >= 0 {
+        if e2 >= 63 || m2 >> (63 - e2) != 0 {
+            return None;
+        }
+        (m2 << e2, 1)
+    } else if -e2 < 64 && m2 & ((1u64 << -e2) - 1) == 0 {
+        (m2 >> -e2, 1)
+    } else {
+        // The interval is (mm, mp) * 2^(e2-2), including both ends if m2 is
+        // even. Its upper end is below 2^(53+e2), so any fraction in it with a
+        // denominator that fits in u64 needs e2 > -117. That keeps 2^(2-e2)
+        // within u128.
+        if e2 <= -117 {
+            return None;
+        }
+        let s = (2 - e2) as u32;
+        let mv = 4 * m2;
+        let mp = mv + 2;
+        let mm = mv - 1 - mm_shift;
+        let accept_bounds = (m2 & 1) == 0;
+        simplest_in_interval(mm as u128, mp as u128, 1u128 << s, accept_bounds)?
+    };
+
+    let numerator = i64::try_from(numerator).ok()?;
+    Some((if sign { -numerator } else { numerator }, denominator))
+}
+
+// Simplest fraction strictly between lo/den and hi/den, or also equal to them
+// if inclusive, with 0 < lo < hi.
+//
+// This expands the continued fractions of both ends in lockstep for as long as
+// their terms agree. At the first term where they differ, the smallest
+// admissible term gives the fraction with the smallest denominator. Each step
+// replaces the ends by the reciprocals of their fractional parts, which keeps
+// every numerator and denominator at most den.
+#[cfg_attr(feature = "no-panic", inline)]
+fn simplest_in_interval(lo: u128, hi: u128, den: u128, inclusive: bool) -> Option<(u64, u64)> {
+    // The current ends are a/b and c/d, or infinity if d is 0. Their inclusion
+    // swaps at every reciprocal.
+    let (mut a, mut b, mut c, mut d) = (lo, den, hi, den);
+    let (mut lo_inclusive, mut hi_inclusive) = (inclusive, inclusive);
+    // Convergents p/q of the terms so far, starting from 1/0 and 0/1.
+    let (mut p0, mut q0, mut p1, mut q1) = (0u64, 1u64, 1u64, 0u64);
+    loop {
+        let floor = a / b;
+        let a_mod = a - floor * b;
+        // hi > lo >= floor, so this does not underflow.
+        let c_mod = c - floor * d;
+        // Whether floor or floor + 1 is in the interval, or neither.
+        let last = a_mod == 0 && lo_inclusive;
+        let next = d == 0 || c_mod > d || c_mod == d && hi_inclusive;
+        let term = u64::try_from(floor + (!last && next) as u128).ok()?;
+        let p = term.checked_mul(p1)?.checked_add(p0)?;
+        let q = term.checked_mul(q1)?.checked_add(q0)?;
+        if last || next {
+            return Some((p, q));
+        }
+        (p0, q0, p1, q1) = (p1, q1, p, q);
+        (a, b, c, d) = (d, c_mod, b, a_mod);
+        (lo_inclusive, hi_inclusive) = (hi_inclusive, lo_inclusive);
+    }
+}
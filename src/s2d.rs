@@ -1,24 +1,253 @@
-use crate::common::{ceil_log2_pow5, log2_pow5};
+use crate::common::{ceil_log2_pow5, decimal_length9, log2_pow5, pow5bits};
 use crate::d2s;
 use crate::d2s_intrinsics::{mul_shift_64, multiple_of_power_of_2, multiple_of_power_of_5};
-use crate::parse::Error;
+use crate::parse::{parse_eight_digits, Error, Exactness, Rounding};
 #[cfg(feature = "no-panic")]
 use no_panic::no_panic;
 
 const DOUBLE_EXPONENT_BIAS: usize = 1023;
 
+// Powers of ten that an f64 represents exactly.
+#[cfg(not(feature = "tiny"))]
+pub static DOUBLE_POW10: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
 fn floor_log2(value: u64) -> u32 {
     63_u32.wrapping_sub(value.leading_zeros())
 }
 
+// Returns the 125 most significant bits of 5^i, rounded down.
+#[cfg_attr(feature = "no-panic", inline)]
+fn double_pow5(i: u32) -> (u64, u64) {
+    #[cfg(any(feature = "small", feature = "tiny"))]
+    unsafe {
+        d2s::compute_pow5(i)
+    }
+    #[cfg(not(any(feature = "small", feature = "tiny")))]
+    {
+        debug_assert!(i < d2s::DOUBLE_POW5_SPLIT.len() as u32);
+        unsafe { *d2s::DOUBLE_POW5_SPLIT.get_unchecked(i as usize) }
+    }
+}
+
+// Returns 2^(pow5bits(i) - 1 + 125) / 5^i, rounded down, plus one.
+#[cfg_attr(feature = "no-panic", inline)]
+fn double_pow5_inv(i: u32) -> (u64, u64) {
+    #[cfg(any(feature = "small", feature = "tiny"))]
+    unsafe {
+        d2s::compute_inv_pow5(i)
+    }
+    #[cfg(not(any(feature = "small", feature = "tiny")))]
+    {
+        debug_assert!(i < d2s::DOUBLE_POW5_INV_SPLIT.len() as u32);
+        unsafe { *d2s::DOUBLE_POW5_INV_SPLIT.get_unchecked(i as usize) }
+    }
+}
+
+// Returns 10^i for i <= 22, which an f64 represents exactly.
+#[cfg_attr(feature = "no-panic", inline)]
+fn double_pow10(i: u32) -> f64 {
+    #[cfg(feature = "tiny")]
+    {
+        // Every partial product is a smaller exact power of ten, so none of
+        // the multiplications round.
+        let mut pow10 = 1.0;
+        for _ in 0..i {
+            pow10 *= 10.0;
+        }
+        pow10
+    }
+    #[cfg(not(feature = "tiny"))]
+    {
+        debug_assert!(i < DOUBLE_POW10.len() as u32);
+        unsafe { *DOUBLE_POW10.get_unchecked(i as usize) }
+    }
+}
+
+// Clinger's fast path. If both m10 and 10^|e10| are exactly representable,
+// one IEEE multiplication or division rounds the exact result correctly.
+#[cfg_attr(feature = "no-panic", inline)]
+fn clinger(m10: u64, e10: i32) -> Option<f64> {
+    // The x87 FPU computes with extra precision, and rounding twice is not
+    // the same as rounding once.
+    if cfg!(all(target_arch = "x86", not(target_feature = "sse2"))) {
+        return None;
+    }
+    if m10 > 1 << (d2s::DOUBLE_MANTISSA_BITS + 1) || e10 < -22 || e10 > 22 {
+        return None;
+    }
+    // Exact, because m10 <= 2^53 was checked above.
+    #[allow(clippy::cast_precision_loss)]
+    let m = m10 as f64;
+    Some(if e10 >= 0 {
+        m * double_pow10(e10 as u32)
+    } else {
+        m / double_pow10(-e10 as u32)
+    })
+}
+
+// The Eisel-Lemire algorithm. Returns the IEEE exponent and mantissa bits of
+// m10 * 10^e10, or None if the 125-bit powers of 5 are not precise enough to
+// decide the rounding, in which case the caller falls back to the exact
+// computation. Requires m10 != 0 and -343 <= e10 <= 308.
+#[cfg_attr(feature = "no-panic", inline)]
+fn eisel_lemire(m10: u64, e10: i32) -> Option<u64> {
+    // Normalize the mantissa so that its most significant bit is set. The
+    // product with the 125-bit power of 5 then has 188 or 189 bits.
+    let lz = m10.leading_zeros();
+    let w = m10 << lz;
+    let (mul, e2) = if e10 >= 0 {
+        // 5^e10 = mul * 2^(pow5bits(e10) - 125), where mul is rounded down.
+        (
+            double_pow5(e10 as u32),
+            pow5bits(e10) - d2s::DOUBLE_POW5_BITCOUNT + e10,
+        )
+    } else {
+        // 5^e10 = mul * 2^-(pow5bits(-e10) - 1 + 125), where mul is rounded up.
+        (
+            double_pow5_inv(-e10 as u32),
+            e10 - pow5bits(-e10) + 1 - d2s::DOUBLE_POW5_INV_BITCOUNT,
+        )
+    };
+    let b0 = w as u128 * mul.0 as u128;
+    let b2 = w as u128 * mul.1 as u128;
+    // The product is hi * 2^64 + lo, and m10 * 10^e10 is approximately
+    // hi * 2^(64 + e2 - lz).
+    let hi = b2 + (b0 >> 64);
+    let upper_bit = (hi >> 124) as u32;
+
+    // Keep the top 54 bits of the product: 53 for the mantissa, plus the one
+    // below it that decides the rounding.
+    let shift = 70 + upper_bit;
+    let mut m2 = (hi >> shift) as u64;
+    let rest = hi & ((1u128 << shift) - 1);
+    let max_rest = (1u128 << shift) - 1;
+
+    // The product is off from w * 5^e10 by less than w < 2^64, so the bits
+    // kept above are only correct if the rest of the product is not all ones
+    // (if mul was rounded down) or all zeros (if mul was rounded up). For
+    // e10 <= 53 the power of 5 fits in 125 bits and the product is exact.
+    let mut exact_tie = false;
+    if e10 > 53 {
+        if rest == max_rest {
+            return None;
+        }
+    } else if e10 >= 0 {
+        // The exact value may be halfway between two floats.
+        exact_tie = rest == 0 && b0 as u64 == 0;
+    } else if rest == 0 {
+        return None;
+    }
+
+    // The biased IEEE exponent the result would have if it were normal.
+    let mut ieee_e2 = e2 - lz as i32
+        + (shift as i32 + 64 + 1)
+        + d2s::DOUBLE_MANTISSA_BITS as i32
+        + DOUBLE_EXPONENT_BIAS as i32;
+    if ieee_e2 <= 0 {
+        // Subnormal. The value is less than 2^-1022 and m10 < 2^64, so e10 is
+        // far too negative for the exact value to be a tie.
+        if 1 - ieee_e2 >= 64 {
+            return Some(0);
+        }
+        m2 >>= 1 - ieee_e2;
+        m2 += m2 & 1;
+        m2 >>= 1;
+        // Rounding up may have produced the smallest normal number.
+        ieee_e2 = (m2 >> d2s::DOUBLE_MANTISSA_BITS) as i32;
+        return Some(
+            (ieee_e2 as u64) << d2s::DOUBLE_MANTISSA_BITS
+                | m2 & ((1u64 << d2s::DOUBLE_MANTISSA_BITS) - 1),
+        );
+    }
+
+    // Round half to even.
+    if exact_tie && m2 & 3 == 1 {
+        m2 &= !1;
+    }
+    m2 += m2 & 1;
+    m2 >>= 1;
+    if m2 >> (d2s::DOUBLE_MANTISSA_BITS + 1) != 0 {
+        // Rounding up carried into the next power of 2.
+        m2 >>= 1;
+        ieee_e2 += 1;
+    }
+    if ieee_e2 >= 0x7ff {
+        return Some(0x7ff_u64 << d2s::DOUBLE_MANTISSA_BITS);
+    }
+    Some(
+        (ieee_e2 as u64) << d2s::DOUBLE_MANTISSA_BITS
+            | m2 & ((1u64 << d2s::DOUBLE_MANTISSA_BITS) - 1),
+    )
+}
+
+// The most significant digits that parsing accepts: enough to read back
+// anything format prints.
+const MAX_DIGITS: i32 = 17;
+
+// Directed rounding also reads back the 18-digit mantissas of
+// Buffer::format_rounded, which still fit in a u64.
+const MAX_DIGITS_DIRECTED: i32 = 18;
+
+// Consumes the digits at buffer[i..] 8 at a time, for as long as they fit in
+// the max_digits significant digits that m10 holds, leaving the rest to the
+// byte loop.
+#[cfg_attr(feature = "no-panic", inline)]
+fn eight_digits(
+    buffer: &[u8],
+    i: &mut usize,
+    m10: &mut u64,
+    m10digits: &mut i32,
+    has_m10: &mut bool,
+    max_digits: i32,
+) {
+    while *m10digits <= max_digits - 8 {
+        let Some(v) = parse_eight_digits(buffer, *i) else {
+            break;
+        };
+        if *m10 != 0 {
+            *m10digits += 8;
+        } else if v != 0 {
+            *m10digits = decimal_length9(v) as i32;
+        }
+        *m10 = 100_000_000 * *m10 + v as u64;
+        *has_m10 = true;
+        *i += 8;
+    }
+}
+
 #[cfg_attr(feature = "no-panic", no_panic)]
 pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
+    let (m10, m10digits, e10, signed_m) = parse_decimal(buffer, MAX_DIGITS)?;
+    Ok(decimal_to_double(m10, m10digits, e10, signed_m))
+}
+
+#[cfg_attr(feature = "no-panic", no_panic)]
+pub fn s2d_exactness(buffer: &[u8], rounding: Rounding) -> Result<(f64, Exactness), Error> {
+    let max_digits = match rounding {
+        Rounding::NearestEven => MAX_DIGITS,
+        Rounding::TowardZero | Rounding::Upward | Rounding::Downward => MAX_DIGITS_DIRECTED,
+    };
+    let (m10, m10digits, e10, signed_m) = parse_decimal(buffer, max_digits)?;
+    Ok(decimal_to_double_exactness(
+        m10, m10digits, e10, signed_m, rounding,
+    ))
+}
+
+// Splits a decimal string into its mantissa m10, the number of digits of m10,
+// the exponent e10, and the sign. At most max_digits significant digits are
+// accepted.
+#[cfg_attr(feature = "no-panic", inline)]
+fn parse_decimal(buffer: &[u8], max_digits: i32) -> Result<(u64, i32, i32, bool), Error> {
     let len = buffer.len();
     if len == 0 {
         return Err(Error::InputTooShort);
     }
 
     let mut m10digits = 0;
+    let mut m10zeros = 0;
     let mut e10digits = 0;
     let mut dot_index = len;
     let mut e_index = len;
@@ -26,6 +255,8 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
     let mut e10 = 0i32;
     let mut signed_m = false;
     let mut signed_e = false;
+    let mut has_m10 = false;
+    let mut has_e10 = false;
 
     let mut i = 0;
     if unsafe { *buffer.get_unchecked(0) } == b'-' {
@@ -33,6 +264,16 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
         i += 1;
     }
 
+    if len - i >= 8 {
+        eight_digits(
+            buffer,
+            &mut i,
+            &mut m10,
+            &mut m10digits,
+            &mut has_m10,
+            max_digits,
+        );
+    }
     while let Some(c) = buffer.get(i).copied() {
         if c == b'.' {
             if dot_index != len {
@@ -40,18 +281,36 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
             }
             dot_index = i;
             i += 1;
+            if len - i >= 8 {
+                eight_digits(
+                    buffer,
+                    &mut i,
+                    &mut m10,
+                    &mut m10digits,
+                    &mut has_m10,
+                    max_digits,
+                );
+            }
             continue;
         }
         if c < b'0' || c > b'9' {
             break;
         }
-        if m10digits >= 17 {
-            return Err(Error::InputTooLong);
+        if m10digits >= max_digits {
+            if c != b'0' {
+                return Err(Error::InputTooLong);
+            }
+            // Zeros beyond the supported number of digits only scale the
+            // mantissa, so they are folded into the exponent instead.
+            m10zeros += 1;
+            i += 1;
+            continue;
         }
         m10 = 10 * m10 + (c - b'0') as u64;
         if m10 != 0 {
             m10digits += 1;
         }
+        has_m10 = true;
         i += 1;
     }
 
@@ -78,43 +337,190 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
             if e10 != 0 {
                 e10digits += 1;
             }
+            has_e10 = true;
             i += 1;
         }
     }
 
-    if i < len {
+    if i < len || !has_m10 || e_index < len && !has_e10 {
         return Err(Error::MalformedInput);
     }
     if signed_e {
         e10 = -e10;
     }
+    e10 += m10zeros;
     e10 -= if dot_index < e_index {
         (e_index - dot_index - 1) as i32
     } else {
         0
     };
+    Ok((m10, m10digits, e10, signed_m))
+}
+
+#[cfg_attr(feature = "no-panic", no_panic)]
+pub fn from_decimal(m10: u64, e10: i32, signed_m: bool) -> f64 {
+    let m10digits = match m10.checked_ilog10() {
+        Some(log) => log as i32 + 1,
+        None => 0,
+    };
+    decimal_to_double(m10, m10digits, e10, signed_m)
+}
+
+// Rounds m10 * 10^e10 to the nearest f64, ties to even. m10digits is the
+// number of decimal digits of m10, at most 20.
+#[cfg_attr(feature = "no-panic", inline)]
+fn decimal_to_double(m10: u64, m10digits: i32, e10: i32, signed_m: bool) -> f64 {
+    if let Some((f, _exactness)) =
+        out_of_range(m10, m10digits, e10, signed_m, Rounding::NearestEven)
+    {
+        return f;
+    }
+    if let Some(f) = clinger(m10, e10) {
+        return if signed_m { -f } else { f };
+    }
+    if let Some(ieee) = eisel_lemire(m10, e10) {
+        let ieee =
+            ieee | (signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS);
+        return f64::from_bits(ieee);
+    }
+    let (m2, e2, trailing_zeros) = decimal_to_binary(m10, e10);
+    round_to_double(m2, e2, trailing_zeros, signed_m, Rounding::NearestEven).0
+}
+
+// Same as decimal_to_double, but in any rounding mode, and also reports how the
+// result relates to the exact value. This skips the fast paths, which round to
+// nearest and do not keep track of exactness.
+#[cfg_attr(feature = "no-panic", inline)]
+fn decimal_to_double_exactness(
+    m10: u64,
+    m10digits: i32,
+    e10: i32,
+    signed_m: bool,
+    rounding: Rounding,
+) -> (f64, Exactness) {
+    if let Some(result) = out_of_range(m10, m10digits, e10, signed_m, rounding) {
+        return result;
+    }
+    let (m2, e2, trailing_zeros) = decimal_to_binary(m10, e10);
+    round_to_double(m2, e2, trailing_zeros, signed_m, rounding)
+}
+
+// Handles zero, and values so far out of range that they become +/-0.0 or
+// +/-Infinity, or the nearest finite float to those in the direction of
+// rounding, without needing the conversion to binary.
+#[cfg_attr(feature = "no-panic", inline)]
+fn out_of_range(
+    m10: u64,
+    m10digits: i32,
+    e10: i32,
+    signed_m: bool,
+    rounding: Rounding,
+) -> Option<(f64, Exactness)> {
     if m10 == 0 {
-        return Ok(if signed_m { -0.0 } else { 0.0 });
+        return Some((if signed_m { -0.0 } else { 0.0 }, Exactness::Exact));
     }
 
-    if m10digits + e10 <= -324 || m10 == 0 {
+    if e10 <= -324 - m10digits {
         // Number is less than 1e-324, which should be rounded down to 0; return
-        // +/-0.0.
-        let ieee = (signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS);
-        return Ok(f64::from_bits(ieee));
+        // +/-0.0, or the smallest subnormal if rounding away from zero.
+        let ieee = ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS))
+            | rounding.away_from_zero(signed_m) as u64;
+        return Some((f64::from_bits(ieee), Exactness::Underflow));
     }
-    if m10digits + e10 >= 310 {
-        // Number is larger than 1e+309, which should be rounded to +/-Infinity.
+    if e10 >= 310 - m10digits {
+        // Number is larger than 1e+309, which should be rounded to +/-Infinity,
+        // or to the largest finite float if rounding toward zero.
+        let ieee = ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS))
+            | ((0x7ff_u64 << d2s::DOUBLE_MANTISSA_BITS) - rounding.toward_zero(signed_m) as u64);
+        return Some((f64::from_bits(ieee), Exactness::Overflow));
+    }
+    None
+}
+
+// Rounds m2 * 2^e2 to an f64, where trailing_zeros tells whether m2 * 2^e2 is
+// the exact value or was rounded down from it.
+#[cfg_attr(feature = "no-panic", inline)]
+fn round_to_double(
+    m2: u64,
+    e2: i32,
+    mut trailing_zeros: bool,
+    signed_m: bool,
+    rounding: Rounding,
+) -> (f64, Exactness) {
+    // Compute the final IEEE exponent.
+    let mut ieee_e2 = i32::max(0, e2 + DOUBLE_EXPONENT_BIAS as i32 + floor_log2(m2) as i32) as u32;
+
+    if ieee_e2 > 0x7fe {
+        // Final IEEE exponent is larger than the maximum representable; return
+        // +/-Infinity, or the largest finite float if rounding toward zero.
         let ieee = ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS))
-            | (0x7ff_u64 << d2s::DOUBLE_MANTISSA_BITS);
-        return Ok(f64::from_bits(ieee));
+            | ((0x7ff_u64 << d2s::DOUBLE_MANTISSA_BITS) - rounding.toward_zero(signed_m) as u64);
+        return (f64::from_bits(ieee), Exactness::Overflow);
     }
 
+    // We need to figure out how much we need to shift m2. The tricky part is
+    // that we need to take the final IEEE exponent into account, so we need to
+    // reverse the bias and also special-case the value 0.
+    let shift = if ieee_e2 == 0 { 1 } else { ieee_e2 as i32 }
+        .wrapping_sub(e2)
+        .wrapping_sub(DOUBLE_EXPONENT_BIAS as i32)
+        .wrapping_sub(d2s::DOUBLE_MANTISSA_BITS as i32);
+    debug_assert!(shift >= 0);
+
+    // We need to round up if the exact value is more than 0.5 above the value
+    // we computed. That's equivalent to checking if the last removed bit was 1
+    // and either the value was not just trailing zeros or the result would
+    // otherwise be odd.
+    //
+    // We need to update trailing_zeros given that we have the exact output
+    // exponent ieee_e2 now.
+    trailing_zeros &= (m2 & ((1_u64 << (shift - 1)) - 1)) == 0;
+    let last_removed_bit = (m2 >> (shift - 1)) & 1;
+    let exact = trailing_zeros && last_removed_bit == 0;
+    let round_up = match rounding {
+        Rounding::NearestEven => {
+            last_removed_bit != 0 && (!trailing_zeros || ((m2 >> shift) & 1) != 0)
+        }
+        // A directed mode rounds the magnitude of every inexact value the
+        // same way, given the sign.
+        _ => !exact && rounding.away_from_zero(signed_m),
+    };
+
+    let mut ieee_m2 = (m2 >> shift).wrapping_add(round_up as u64);
+    debug_assert!(ieee_m2 <= 1_u64 << (d2s::DOUBLE_MANTISSA_BITS + 1));
+    ieee_m2 &= (1_u64 << d2s::DOUBLE_MANTISSA_BITS) - 1;
+    if ieee_m2 == 0 && round_up {
+        // Due to how the IEEE represents +/-Infinity, we don't need to check
+        // for overflow here.
+        ieee_e2 += 1;
+    }
+    let ieee = ((((signed_m as u64) << d2s::DOUBLE_EXPONENT_BITS) | ieee_e2 as u64)
+        << d2s::DOUBLE_MANTISSA_BITS)
+        | ieee_m2;
+
+    let exactness = if exact {
+        Exactness::Exact
+    } else if ieee_e2 == 0 {
+        Exactness::Underflow
+    } else if ieee_e2 == 0x7ff {
+        Exactness::Overflow
+    } else {
+        Exactness::rounded(round_up, signed_m)
+    };
+    (f64::from_bits(ieee), exactness)
+}
+
+// Converts m10 * 10^e10 to m2 * 2^e2 rounded down, where m2 has
+// DOUBLE_MANTISSA_BITS + 2 or DOUBLE_MANTISSA_BITS + 3 significant bits, and
+// reports whether the conversion was exact. Requires m10 != 0 and
+// -343 <= e10 <= 325.
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn decimal_to_binary(m10: u64, e10: i32) -> (u64, i32, bool) {
     // Convert to binary float m2 * 2^e2, while retaining information about
     // whether the conversion was exact (trailing_zeros).
     let e2: i32;
     let m2: u64;
-    let mut trailing_zeros: bool;
+    let trailing_zeros: bool;
     if e10 >= 0 {
         // The length of m * 10^e in bits is:
         //   log2(m10 * 10^e10) = log2(m10) + e10 log2(10) = log2(m10) + e10 + e10 * log2(5)
@@ -138,12 +544,7 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
             .wrapping_sub(ceil_log2_pow5(e10))
             .wrapping_add(d2s::DOUBLE_POW5_BITCOUNT);
         debug_assert!(j >= 0);
-        debug_assert!(e10 < d2s::DOUBLE_POW5_SPLIT.len() as i32);
-        m2 = mul_shift_64(
-            m10,
-            unsafe { d2s::DOUBLE_POW5_SPLIT.get_unchecked(e10 as usize) },
-            j as u32,
-        );
+        m2 = mul_shift_64(m10, &double_pow5(e10 as u32), j as u32);
 
         // We also compute if the result is exact, i.e.,
         //   [m10 * 10^e10 / 2^e2] == m10 * 10^e10 / 2^e2.
@@ -163,55 +564,12 @@ pub fn s2d(buffer: &[u8]) -> Result<f64, Error> {
             .wrapping_add(ceil_log2_pow5(-e10))
             .wrapping_sub(1)
             .wrapping_add(d2s::DOUBLE_POW5_INV_BITCOUNT);
-        debug_assert!(-e10 < d2s::DOUBLE_POW5_INV_SPLIT.len() as i32);
-        m2 = mul_shift_64(
-            m10,
-            unsafe { d2s::DOUBLE_POW5_INV_SPLIT.get_unchecked(-e10 as usize) },
-            j as u32,
-        );
-        trailing_zeros = multiple_of_power_of_5(m10, -e10 as u32);
-    }
-
-    // Compute the final IEEE exponent.
-    let mut ieee_e2 = i32::max(0, e2 + DOUBLE_EXPONENT_BIAS as i32 + floor_log2(m2) as i32) as u32;
-
-    if ieee_e2 > 0x7fe {
-        // Final IEEE exponent is larger than the maximum representable; return +/-Infinity.
-        let ieee = ((signed_m as u64) << (d2s::DOUBLE_EXPONENT_BITS + d2s::DOUBLE_MANTISSA_BITS))
-            | (0x7ff_u64 << d2s::DOUBLE_MANTISSA_BITS);
-        return Ok(f64::from_bits(ieee));
+        m2 = mul_shift_64(m10, &double_pow5_inv(-e10 as u32), j as u32);
+        // A mantissa of more than 17 digits may also need to be divisible by
+        // 2^(e2-e10) for the result to be exact.
+        trailing_zeros = (e2 < e10
+            || e2 - e10 < 64 && multiple_of_power_of_2(m10, (e2 - e10) as u32))
+            && multiple_of_power_of_5(m10, -e10 as u32);
     }
-
-    // We need to figure out how much we need to shift m2. The tricky part is
-    // that we need to take the final IEEE exponent into account, so we need to
-    // reverse the bias and also special-case the value 0.
-    let shift = if ieee_e2 == 0 { 1 } else { ieee_e2 as i32 }
-        .wrapping_sub(e2)
-        .wrapping_sub(DOUBLE_EXPONENT_BIAS as i32)
-        .wrapping_sub(d2s::DOUBLE_MANTISSA_BITS as i32);
-    debug_assert!(shift >= 0);
-
-    // We need to round up if the exact value is more than 0.5 above the value
-    // we computed. That's equivalent to checking if the last removed bit was 1
-    // and either the value was not just trailing zeros or the result would
-    // otherwise be odd.
-    //
-    // We need to update trailing_zeros given that we have the exact output
-    // exponent ieee_e2 now.
-    trailing_zeros &= (m2 & ((1_u64 << (shift - 1)) - 1)) == 0;
-    let last_removed_bit = (m2 >> (shift - 1)) & 1;
-    let round_up = last_removed_bit != 0 && (!trailing_zeros || ((m2 >> shift) & 1) != 0);
-
-    let mut ieee_m2 = (m2 >> shift).wrapping_add(round_up as u64);
-    debug_assert!(ieee_m2 <= 1_u64 << (d2s::DOUBLE_MANTISSA_BITS + 1));
-    ieee_m2 &= (1_u64 << d2s::DOUBLE_MANTISSA_BITS) - 1;
-    if ieee_m2 == 0 && round_up {
-        // Due to how the IEEE represents +/-Infinity, we don't need to check
-        // for overflow here.
-        ieee_e2 += 1;
-    }
-    let ieee = ((((signed_m as u64) << d2s::DOUBLE_EXPONENT_BITS) | ieee_e2 as u64)
-        << d2s::DOUBLE_MANTISSA_BITS)
-        | ieee_m2;
-    Ok(f64::from_bits(ieee))
+    (m2, e2, trailing_zeros)
 }
@@ -1,26 +1,69 @@
-use crate::common::{ceil_log2_pow5, log2_pow5};
+use crate::common::{ceil_log2_pow5, decimal_length9, log2_pow5};
 use crate::f2s;
 use crate::f2s_intrinsics::{
     mul_pow5_div_pow2, mul_pow5_inv_div_pow2, multiple_of_power_of_2_32, multiple_of_power_of_5_32,
 };
-use crate::parse::Error;
+use crate::parse::{parse_eight_digits, Error, Exactness, Rounding};
+use crate::s2d;
 #[cfg(feature = "no-panic")]
 use no_panic::no_panic;
 
 const FLOAT_EXPONENT_BIAS: usize = 127;
 
-fn floor_log2(value: u32) -> u32 {
-    31_u32.wrapping_sub(value.leading_zeros())
+fn floor_log2(value: u64) -> u32 {
+    63_u32.wrapping_sub(value.leading_zeros())
+}
+
+// Consumes the digits at buffer[i..] 8 at a time, for as long as they fit in
+// the 9 significant digits that m10 holds, leaving the rest to the byte loop.
+#[cfg_attr(feature = "no-panic", inline)]
+fn eight_digits(
+    buffer: &[u8],
+    i: &mut usize,
+    m10: &mut u32,
+    m10digits: &mut i32,
+    has_m10: &mut bool,
+) {
+    while *m10digits <= 9 - 8 {
+        let Some(v) = parse_eight_digits(buffer, *i) else {
+            break;
+        };
+        if *m10 != 0 {
+            *m10digits += 8;
+        } else if v != 0 {
+            *m10digits = decimal_length9(v) as i32;
+        }
+        *m10 = 100_000_000 * *m10 + v;
+        *has_m10 = true;
+        *i += 8;
+    }
 }
 
 #[cfg_attr(feature = "no-panic", no_panic)]
 pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
+    let (m10, m10digits, e10, signed_m) = parse_decimal(buffer)?;
+    Ok(decimal_to_float(m10 as u64, m10digits, e10, signed_m, Rounding::NearestEven).0)
+}
+
+#[cfg_attr(feature = "no-panic", no_panic)]
+pub fn s2f_exactness(buffer: &[u8], rounding: Rounding) -> Result<(f32, Exactness), Error> {
+    let (m10, m10digits, e10, signed_m) = parse_decimal(buffer)?;
+    Ok(decimal_to_float(
+        m10 as u64, m10digits, e10, signed_m, rounding,
+    ))
+}
+
+// Splits a decimal string into its mantissa m10, the number of digits of m10,
+// the exponent e10, and the sign.
+#[cfg_attr(feature = "no-panic", inline)]
+fn parse_decimal(buffer: &[u8]) -> Result<(u32, i32, i32, bool), Error> {
     let len = buffer.len();
     if len == 0 {
         return Err(Error::InputTooShort);
     }
 
     let mut m10digits = 0;
+    let mut m10zeros = 0;
     let mut e10digits = 0;
     let mut dot_index = len;
     let mut e_index = len;
@@ -28,6 +71,8 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
     let mut e10 = 0i32;
     let mut signed_m = false;
     let mut signed_e = false;
+    let mut has_m10 = false;
+    let mut has_e10 = false;
 
     let mut i = 0;
     if unsafe { *buffer.get_unchecked(0) } == b'-' {
@@ -35,6 +80,9 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
         i += 1;
     }
 
+    if len - i >= 8 {
+        eight_digits(buffer, &mut i, &mut m10, &mut m10digits, &mut has_m10);
+    }
     while let Some(c) = buffer.get(i).copied() {
         if c == b'.' {
             if dot_index != len {
@@ -42,18 +90,29 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
             }
             dot_index = i;
             i += 1;
+            if len - i >= 8 {
+                eight_digits(buffer, &mut i, &mut m10, &mut m10digits, &mut has_m10);
+            }
             continue;
         }
         if c < b'0' || c > b'9' {
             break;
         }
         if m10digits >= 9 {
-            return Err(Error::InputTooLong);
+            if c != b'0' {
+                return Err(Error::InputTooLong);
+            }
+            // Zeros beyond the supported number of digits only scale the
+            // mantissa, so they are folded into the exponent instead.
+            m10zeros += 1;
+            i += 1;
+            continue;
         }
         m10 = 10 * m10 + (c - b'0') as u32;
         if m10 != 0 {
             m10digits += 1;
         }
+        has_m10 = true;
         i += 1;
     }
 
@@ -80,43 +139,86 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
             if e10 != 0 {
                 e10digits += 1;
             }
+            has_e10 = true;
             i += 1;
         }
     }
 
-    if i < len {
+    if i < len || !has_m10 || e_index < len && !has_e10 {
         return Err(Error::MalformedInput);
     }
     if signed_e {
         e10 = -e10;
     }
+    e10 += m10zeros;
     e10 -= if dot_index < e_index {
         (e_index - dot_index - 1) as i32
     } else {
         0
     };
+    Ok((m10, m10digits, e10, signed_m))
+}
+
+#[cfg_attr(feature = "no-panic", no_panic)]
+pub fn from_decimal(m10: u64, e10: i32, signed_m: bool) -> f32 {
+    let m10digits = match m10.checked_ilog10() {
+        Some(log) => log as i32 + 1,
+        None => 0,
+    };
+    decimal_to_float(m10, m10digits, e10, signed_m, Rounding::NearestEven).0
+}
+
+// Rounds m10 * 10^e10 to an f32, and reports how the result relates to the
+// exact value. m10digits is the number of decimal digits of m10, at most 20.
+#[cfg_attr(feature = "no-panic", inline)]
+fn decimal_to_float(
+    m10: u64,
+    m10digits: i32,
+    e10: i32,
+    signed_m: bool,
+    rounding: Rounding,
+) -> (f32, Exactness) {
     if m10 == 0 {
-        return Ok(if signed_m { -0.0 } else { 0.0 });
+        return (if signed_m { -0.0 } else { 0.0 }, Exactness::Exact);
     }
 
-    if m10digits + e10 <= -46 || m10 == 0 {
+    if e10 <= -46 - m10digits {
         // Number is less than 1e-46, which should be rounded down to 0; return
-        // +/-0.0.
-        let ieee = (signed_m as u32) << (f2s::FLOAT_EXPONENT_BITS + f2s::FLOAT_MANTISSA_BITS);
-        return Ok(f32::from_bits(ieee));
+        // +/-0.0, or the smallest subnormal if rounding away from zero.
+        let ieee = ((signed_m as u32) << (f2s::FLOAT_EXPONENT_BITS + f2s::FLOAT_MANTISSA_BITS))
+            | rounding.away_from_zero(signed_m) as u32;
+        return (f32::from_bits(ieee), Exactness::Underflow);
     }
-    if m10digits + e10 >= 40 {
-        // Number is larger than 1e+39, which should be rounded to +/-Infinity.
+    if e10 >= 40 - m10digits {
+        // Number is larger than 1e+39, which should be rounded to +/-Infinity,
+        // or to the largest finite float if rounding toward zero.
         let ieee = ((signed_m as u32) << (f2s::FLOAT_EXPONENT_BITS + f2s::FLOAT_MANTISSA_BITS))
-            | (0xff_u32 << f2s::FLOAT_MANTISSA_BITS);
-        return Ok(f32::from_bits(ieee));
+            | ((0xff_u32 << f2s::FLOAT_MANTISSA_BITS) - rounding.toward_zero(signed_m) as u32);
+        return (f32::from_bits(ieee), Exactness::Overflow);
     }
 
+    let (m2, e2, trailing_zeros) = if m10digits <= 9 {
+        let (m2, e2, trailing_zeros) = decimal_to_binary(m10 as u32, e10);
+        (m2 as u64, e2, trailing_zeros)
+    } else {
+        // A longer mantissa goes through the wider conversion of s2d, whose
+        // extra bits are rounded off the same way.
+        s2d::decimal_to_binary(m10, e10)
+    };
+    round_to_float(m2, e2, trailing_zeros, signed_m, rounding)
+}
+
+// Converts m10 * 10^e10 to m2 * 2^e2 rounded down, where m2 has
+// FLOAT_MANTISSA_BITS + 2 or FLOAT_MANTISSA_BITS + 3 significant bits, and
+// reports whether the conversion was exact. Requires m10 != 0 and
+// -54 <= e10 <= 38.
+#[cfg_attr(feature = "no-panic", inline)]
+fn decimal_to_binary(m10: u32, e10: i32) -> (u32, i32, bool) {
     // Convert to binary float m2 * 2^e2, while retaining information about
     // whether the conversion was exact (trailing_zeros).
     let e2: i32;
     let m2: u32;
-    let mut trailing_zeros: bool;
+    let trailing_zeros: bool;
     if e10 >= 0 {
         // The length of m * 10^e in bits is:
         //   log2(m10 * 10^e10) = log2(m10) + e10 log2(10) = log2(m10) + e10 + e10 * log2(5)
@@ -128,7 +230,7 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
         //
         // We use floor(log2(5^e10)) so that we get at least this many bits; better to
         // have an additional bit than to not have enough bits.
-        e2 = floor_log2(m10)
+        e2 = floor_log2(m10 as u64)
             .wrapping_add(e10 as u32)
             .wrapping_add(log2_pow5(e10) as u32)
             .wrapping_sub(f2s::FLOAT_MANTISSA_BITS + 1) as i32;
@@ -151,7 +253,7 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
         trailing_zeros =
             e2 < e10 || e2 - e10 < 32 && multiple_of_power_of_2_32(m10, (e2 - e10) as u32);
     } else {
-        e2 = floor_log2(m10)
+        e2 = floor_log2(m10 as u64)
             .wrapping_add(e10 as u32)
             .wrapping_sub(ceil_log2_pow5(-e10) as u32)
             .wrapping_sub(f2s::FLOAT_MANTISSA_BITS + 1) as i32;
@@ -179,16 +281,29 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
             || (e2 - e10 < 32 && multiple_of_power_of_2_32(m10, (e2 - e10) as u32)))
             && multiple_of_power_of_5_32(m10, -e10 as u32);
     }
+    (m2, e2, trailing_zeros)
+}
 
+// Rounds m2 * 2^e2 to an f32, where trailing_zeros tells whether m2 * 2^e2 is
+// the exact value or was rounded down from it. m2 must have at least
+// FLOAT_MANTISSA_BITS + 2 significant bits.
+#[cfg_attr(feature = "no-panic", inline)]
+fn round_to_float(
+    m2: u64,
+    e2: i32,
+    mut trailing_zeros: bool,
+    signed_m: bool,
+    rounding: Rounding,
+) -> (f32, Exactness) {
     // Compute the final IEEE exponent.
     let mut ieee_e2 = i32::max(0, e2 + FLOAT_EXPONENT_BIAS as i32 + floor_log2(m2) as i32) as u32;
 
     if ieee_e2 > 0xfe {
         // Final IEEE exponent is larger than the maximum representable; return
-        // +/-Infinity.
+        // +/-Infinity, or the largest finite float if rounding toward zero.
         let ieee = ((signed_m as u32) << (f2s::FLOAT_EXPONENT_BITS + f2s::FLOAT_MANTISSA_BITS))
-            | (0xff_u32 << f2s::FLOAT_MANTISSA_BITS);
-        return Ok(f32::from_bits(ieee));
+            | ((0xff_u32 << f2s::FLOAT_MANTISSA_BITS) - rounding.toward_zero(signed_m) as u32);
+        return (f32::from_bits(ieee), Exactness::Overflow);
     }
 
     // We need to figure out how much we need to shift m2. The tricky part is
@@ -207,11 +322,19 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
     //
     // We need to update trailing_zeros given that we have the exact output
     // exponent ieee_e2 now.
-    trailing_zeros &= (m2 & ((1_u32 << (shift - 1)) - 1)) == 0;
+    trailing_zeros &= (m2 & ((1_u64 << (shift - 1)) - 1)) == 0;
     let last_removed_bit = (m2 >> (shift - 1)) & 1;
-    let round_up = last_removed_bit != 0 && (!trailing_zeros || ((m2 >> shift) & 1) != 0);
+    let exact = trailing_zeros && last_removed_bit == 0;
+    let round_up = match rounding {
+        Rounding::NearestEven => {
+            last_removed_bit != 0 && (!trailing_zeros || ((m2 >> shift) & 1) != 0)
+        }
+        // A directed mode rounds the magnitude of every inexact value the
+        // same way, given the sign.
+        _ => !exact && rounding.away_from_zero(signed_m),
+    };
 
-    let mut ieee_m2 = (m2 >> shift).wrapping_add(round_up as u32);
+    let mut ieee_m2 = ((m2 >> shift) as u32).wrapping_add(round_up as u32);
     debug_assert!(ieee_m2 <= 1_u32 << (f2s::FLOAT_MANTISSA_BITS + 1));
     ieee_m2 &= (1_u32 << f2s::FLOAT_MANTISSA_BITS) - 1;
     if ieee_m2 == 0 && round_up {
@@ -225,5 +348,15 @@ pub fn s2f(buffer: &[u8]) -> Result<f32, Error> {
     let ieee = ((((signed_m as u32) << f2s::FLOAT_EXPONENT_BITS) | ieee_e2)
         << f2s::FLOAT_MANTISSA_BITS)
         | ieee_m2;
-    Ok(f32::from_bits(ieee))
+
+    let exactness = if exact {
+        Exactness::Exact
+    } else if ieee_e2 == 0 {
+        Exactness::Underflow
+    } else if ieee_e2 == 0xff {
+        Exactness::Overflow
+    } else {
+        Exactness::rounded(round_up, signed_m)
+    };
+    (f32::from_bits(ieee), exactness)
 }
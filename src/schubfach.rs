@@ -0,0 +1,318 @@
+// Shortest decimal conversion using Raffaello Giulietti's Schubfach algorithm,
+// as described in "The Schubfach way to render doubles" (2020). This is a
+// drop-in replacement for d2d and f2d: for every input it produces the same
+// FloatingDecimal64 and FloatingDecimal32 as Ryū, which is to say the
+// shortest decimal in the rounding interval, the closest one to the exact
+// value if there are several, with ties broken toward an even mantissa, and
+// with no trailing zeros.
+//
+// Where Ryū computes the interval bounds at a precision that leaves room to
+// strip digits one at a time, Schubfach scales the bounds by a single power of
+// ten chosen so that at most two candidate lengths need to be considered.
+//
+// Besides d2d and f2d, which look the power of ten up in a table, there are
+// const fn versions that compute it from scratch, for formatting at compile
+// time. The tables themselves are built at compile time by those same const
+// fns. With the "tiny" feature there are no tables, and d2d and f2d compute
+// the power of ten at runtime too.
+
+use crate::d2s::{FloatingDecimal64, DOUBLE_BIAS, DOUBLE_MANTISSA_BITS};
+use crate::d2s_intrinsics::BigPow5;
+use crate::f2s::{FloatingDecimal32, FLOAT_BIAS, FLOAT_MANTISSA_BITS};
+
+pub const POW10_MIN_EXPONENT64: i32 = -292;
+pub const POW10_MAX_EXPONENT64: i32 = 324;
+pub const POW10_MIN_EXPONENT32: i32 = -31;
+pub const POW10_MAX_EXPONENT32: i32 = 45;
+
+#[cfg(not(feature = "tiny"))]
+const POW10_TABLE_SIZE64: usize = (POW10_MAX_EXPONENT64 - POW10_MIN_EXPONENT64 + 1) as usize;
+#[cfg(not(feature = "tiny"))]
+const POW10_TABLE_SIZE32: usize = (POW10_MAX_EXPONENT32 - POW10_MIN_EXPONENT32 + 1) as usize;
+
+// Entry e - MIN is compute_pow10_significand64(e) or
+// compute_pow10_significand32(e), for every power of 10 that scale64 and
+// scale32 can ask for.
+
+#[cfg(not(feature = "tiny"))]
+pub static POW10_SIGNIFICANDS64: [(u64, u64); POW10_TABLE_SIZE64] = {
+    let mut table = [(0, 0); POW10_TABLE_SIZE64];
+    let mut i = 0;
+    while i < POW10_TABLE_SIZE64 {
+        table[i] = compute_pow10_significand64(POW10_MIN_EXPONENT64 + i as i32);
+        i += 1;
+    }
+    table
+};
+
+#[cfg(not(feature = "tiny"))]
+pub static POW10_SIGNIFICANDS32: [u64; POW10_TABLE_SIZE32] = {
+    let mut table = [0; POW10_TABLE_SIZE32];
+    let mut i = 0;
+    while i < POW10_TABLE_SIZE32 {
+        table[i] = compute_pow10_significand32(POW10_MIN_EXPONENT32 + i as i32);
+        i += 1;
+    }
+    table
+};
+
+// floor(log2(10^e)) for -1233 <= e <= 1233.
+#[cfg_attr(feature = "no-panic", inline)]
+const fn floor_log2_pow10(e: i32) -> i32 {
+    debug_assert!(-1233 <= e && e <= 1233);
+    (e * 1741647) >> 19
+}
+
+// floor(log10(2^e)) for -2620 <= e <= 2620.
+#[cfg_attr(feature = "no-panic", inline)]
+const fn floor_log10_pow2(e: i32) -> i32 {
+    debug_assert!(-2620 <= e && e <= 2620);
+    (e * 1262611) >> 22
+}
+
+// floor(log10(3/4 * 2^e)) for -2985 <= e <= 2936.
+#[cfg_attr(feature = "no-panic", inline)]
+const fn floor_log10_three_quarters_pow2(e: i32) -> i32 {
+    debug_assert!(-2985 <= e && e <= 2936);
+    (e * 1262611 - 524031) >> 22
+}
+
+// The top 64 bits of g * cp, where g is 128 bits, with the lowest bit set if
+// any of the discarded bits beyond the next 64 are nonzero or the next 64 bits
+// are more than 1. This rounds to odd, which is enough to keep the comparisons
+// below exact.
+#[cfg_attr(feature = "no-panic", inline)]
+const fn round_to_odd64(g: (u64, u64), cp: u64) -> u64 {
+    let (lo, hi) = g;
+    let x = (lo as u128 * cp as u128) >> 64;
+    let y = hi as u128 * cp as u128 + x;
+    let y1 = (y >> 64) as u64;
+    let y0 = y as u64;
+    y1 | (y0 > 1) as u64
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+const fn round_to_odd32(g: u64, cp: u32) -> u32 {
+    let p = g as u128 * cp as u128;
+    let y1 = (p >> 64) as u32;
+    let y0 = (p >> 32) as u32;
+    y1 | (y0 > 1) as u32
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn d2d(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
+    let k = scale64(ieee_mantissa, ieee_exponent);
+    debug_assert!(POW10_MIN_EXPONENT64 <= -k && -k <= POW10_MAX_EXPONENT64);
+    #[cfg(feature = "tiny")]
+    let g = compute_pow10_significand64(-k);
+    #[cfg(not(feature = "tiny"))]
+    let g = unsafe { *POW10_SIGNIFICANDS64.get_unchecked((-k - POW10_MIN_EXPONENT64) as usize) };
+    to_decimal64(ieee_mantissa, ieee_exponent, k, g)
+}
+
+pub const fn d2d_const(ieee_mantissa: u64, ieee_exponent: u32) -> FloatingDecimal64 {
+    let k = scale64(ieee_mantissa, ieee_exponent);
+    to_decimal64(
+        ieee_mantissa,
+        ieee_exponent,
+        k,
+        compute_pow10_significand64(-k),
+    )
+}
+
+// The significand and binary exponent of an f64, such that its value is
+// c * 2^q.
+#[cfg_attr(feature = "no-panic", inline)]
+const fn decode64(ieee_mantissa: u64, ieee_exponent: u32) -> (u64, i32) {
+    if ieee_exponent == 0 {
+        (ieee_mantissa, 1 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32)
+    } else {
+        (
+            (1u64 << DOUBLE_MANTISSA_BITS) | ieee_mantissa,
+            ieee_exponent as i32 - DOUBLE_BIAS - DOUBLE_MANTISSA_BITS as i32,
+        )
+    }
+}
+
+// Scale by 10^-k, with k chosen so that the rounding interval is at least 1
+// and less than 10 units wide.
+#[cfg_attr(feature = "no-panic", inline)]
+const fn scale64(ieee_mantissa: u64, ieee_exponent: u32) -> i32 {
+    let (_, q) = decode64(ieee_mantissa, ieee_exponent);
+    if ieee_mantissa == 0 && ieee_exponent > 1 {
+        floor_log10_three_quarters_pow2(q)
+    } else {
+        floor_log10_pow2(q)
+    }
+}
+
+// ceil(10^e / 2^(floor_log2_pow10(e) + 1 - 128)), so that the top bit is
+// always set.
+#[cfg_attr(feature = "no-panic", inline)]
+pub const fn compute_pow10_significand64(e: i32) -> (u64, u64) {
+    // 10^e is 5^e shifted, so the significand is that of 5^e for e >= 0. For
+    // e < 0 it is the reciprocal of 5^-e, which is never exact.
+    let value = if e >= 0 {
+        BigPow5::new(e as u32).top128_ceil()
+    } else {
+        BigPow5::new(-e as u32).inverse(128) + 1
+    };
+    (value as u64, (value >> 64) as u64)
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+const fn to_decimal64(
+    ieee_mantissa: u64,
+    ieee_exponent: u32,
+    k: i32,
+    g: (u64, u64),
+) -> FloatingDecimal64 {
+    let (c, q) = decode64(ieee_mantissa, ieee_exponent);
+    let even = c % 2 == 0;
+    let lower_boundary_is_closer = ieee_mantissa == 0 && ieee_exponent > 1;
+
+    // The rounding interval and the value itself, times 4.
+    let cbl = 4 * c - 2 + lower_boundary_is_closer as u64;
+    let cb = 4 * c;
+    let cbr = 4 * c + 2;
+
+    let h = q + floor_log2_pow10(-k) + 1;
+    debug_assert!(1 <= h && h <= 4);
+    let vbl = round_to_odd64(g, cbl << h);
+    let vb = round_to_odd64(g, cb << h);
+    let vbr = round_to_odd64(g, cbr << h);
+    let lower = vbl + !even as u64;
+    let upper = vbr - !even as u64;
+
+    // Prefer a candidate with one digit fewer, a multiple of 10 units. The
+    // interval is narrower than 10 units, so at most one of them is inside.
+    let s = vb / 4;
+    if s >= 10 {
+        let sp = s / 10;
+        let up_inside = lower <= 40 * sp;
+        let wp_inside = 40 * sp + 40 <= upper;
+        if up_inside != wp_inside {
+            return remove_trailing_zeros64(sp + wp_inside as u64, k + 1);
+        }
+    }
+
+    // Otherwise take whichever of the two closest units is inside, or the
+    // nearer one if both are.
+    let u_inside = lower <= 4 * s;
+    let w_inside = 4 * s + 4 <= upper;
+    if u_inside != w_inside {
+        return remove_trailing_zeros64(s + w_inside as u64, k);
+    }
+    let mid = 4 * s + 2;
+    let round_up = vb > mid || (vb == mid && (s & 1) != 0);
+    remove_trailing_zeros64(s + round_up as u64, k)
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+pub fn f2d(ieee_mantissa: u32, ieee_exponent: u32) -> FloatingDecimal32 {
+    let k = scale32(ieee_mantissa, ieee_exponent);
+    debug_assert!(POW10_MIN_EXPONENT32 <= -k && -k <= POW10_MAX_EXPONENT32);
+    #[cfg(feature = "tiny")]
+    let g = compute_pow10_significand32(-k);
+    #[cfg(not(feature = "tiny"))]
+    let g = unsafe { *POW10_SIGNIFICANDS32.get_unchecked((-k - POW10_MIN_EXPONENT32) as usize) };
+    to_decimal32(ieee_mantissa, ieee_exponent, k, g)
+}
+
+pub const fn f2d_const(ieee_mantissa: u32, ieee_exponent: u32) -> FloatingDecimal32 {
+    let k = scale32(ieee_mantissa, ieee_exponent);
+    to_decimal32(
+        ieee_mantissa,
+        ieee_exponent,
+        k,
+        compute_pow10_significand32(-k),
+    )
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+const fn decode32(ieee_mantissa: u32, ieee_exponent: u32) -> (u32, i32) {
+    if ieee_exponent == 0 {
+        (ieee_mantissa, 1 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32)
+    } else {
+        (
+            (1u32 << FLOAT_MANTISSA_BITS) | ieee_mantissa,
+            ieee_exponent as i32 - FLOAT_BIAS - FLOAT_MANTISSA_BITS as i32,
+        )
+    }
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+const fn scale32(ieee_mantissa: u32, ieee_exponent: u32) -> i32 {
+    let (_, q) = decode32(ieee_mantissa, ieee_exponent);
+    if ieee_mantissa == 0 && ieee_exponent > 1 {
+        floor_log10_three_quarters_pow2(q)
+    } else {
+        floor_log10_pow2(q)
+    }
+}
+
+// ceil(10^e / 2^(floor_log2_pow10(e) + 1 - 64)), so that the top bit is
+// always set.
+#[cfg_attr(feature = "no-panic", inline)]
+pub const fn compute_pow10_significand32(e: i32) -> u64 {
+    let (lo, hi) = compute_pow10_significand64(e);
+    hi + (lo != 0) as u64
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+const fn to_decimal32(ieee_mantissa: u32, ieee_exponent: u32, k: i32, g: u64) -> FloatingDecimal32 {
+    let (c, q) = decode32(ieee_mantissa, ieee_exponent);
+    let even = c % 2 == 0;
+    let lower_boundary_is_closer = ieee_mantissa == 0 && ieee_exponent > 1;
+
+    let cbl = 4 * c - 2 + lower_boundary_is_closer as u32;
+    let cb = 4 * c;
+    let cbr = 4 * c + 2;
+
+    let h = q + floor_log2_pow10(-k) + 1;
+    debug_assert!(1 <= h && h <= 4);
+    let vbl = round_to_odd32(g, cbl << h);
+    let vb = round_to_odd32(g, cb << h);
+    let vbr = round_to_odd32(g, cbr << h);
+    let lower = vbl + !even as u32;
+    let upper = vbr - !even as u32;
+
+    let s = vb / 4;
+    if s >= 10 {
+        let sp = s / 10;
+        let up_inside = lower <= 40 * sp;
+        let wp_inside = 40 * sp + 40 <= upper;
+        if up_inside != wp_inside {
+            return remove_trailing_zeros32(sp + wp_inside as u32, k + 1);
+        }
+    }
+
+    let u_inside = lower <= 4 * s;
+    let w_inside = 4 * s + 4 <= upper;
+    if u_inside != w_inside {
+        return remove_trailing_zeros32(s + w_inside as u32, k);
+    }
+    let mid = 4 * s + 2;
+    let round_up = vb > mid || (vb == mid && (s & 1) != 0);
+    remove_trailing_zeros32(s + round_up as u32, k)
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+const fn remove_trailing_zeros64(mut mantissa: u64, mut exponent: i32) -> FloatingDecimal64 {
+    debug_assert!(mantissa != 0);
+    while mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    FloatingDecimal64 { mantissa, exponent }
+}
+
+#[cfg_attr(feature = "no-panic", inline)]
+const fn remove_trailing_zeros32(mut mantissa: u32, mut exponent: i32) -> FloatingDecimal32 {
+    debug_assert!(mantissa != 0);
+    while mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    FloatingDecimal32 { mantissa, exponent }
+}
@@ -0,0 +1,247 @@
+//! Helpers for serializing floating point numbers as Ryū strings with Serde.
+//!
+//! Text formats such as CSV, query strings and XML attributes represent every
+//! value as a string. The modules in here plug into `#[serde(with = "...")]`
+//! to serialize an f32 or f64 field as the string produced by
+//! [`Buffer::format`], and to deserialize it using [`ryu::parse`].
+//!
+//! The modules differ only in how NaN and the infinities are handled:
+//!
+//! - [`as_str`] writes them as "NaN", "inf" and "-inf" and reads those back;
+//! - [`as_str::finite`] refuses to serialize them, and refuses to deserialize
+//!   any string that does not parse to a finite number;
+//! - [`as_str::null_nonfinite`] serializes them as none, which most formats
+//!   write as null or an empty field, and deserializes none as NaN.
+//!
+//! Reading goes through [`ryu::parse`] and shares its limit of 17 significant
+//! digits for f64 and 9 for f32. That is enough for anything Ryū prints, but
+//! other tools may write more, such as "3.14159265358979323846", and those
+//! strings fail to deserialize.
+//!
+//! [`Buffer::format`]: crate::Buffer::format
+//! [`ryu::parse`]: crate::parse
+//!
+//! ## Example
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Reading {
+//!     #[serde(with = "ryu::serde::as_str")]
+//!     value: f64,
+//!     #[serde(with = "ryu::serde::as_str::finite")]
+//!     scale: f32,
+//! }
+//!
+//! let reading = Reading { value: 1e-7, scale: 0.5 };
+//! let json = serde_json::to_string(&reading).unwrap();
+//! assert_eq!(json, r#"{"value":"1e-7","scale":"0.5"}"#);
+//! assert_eq!(serde_json::from_str::<Reading>(&json).unwrap(), reading);
+//! ```
+
+use crate::{Buffer, Float};
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::ser::{self, Serializer};
+
+/// Serialize a float as its Ryū string, with NaN and the infinities written as
+/// "NaN", "inf" and "-inf".
+///
+/// Use with `#[serde(with = "ryu::serde::as_str")]`. See the [module
+/// documentation][crate::serde] for the other ways of handling non-finite
+/// values.
+pub mod as_str {
+    use super::NonFinite;
+    use crate::Float;
+    use serde::{Deserializer, Serializer};
+
+    /// Serialize a float as its Ryū string.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the error from `serializer`, if serializing the string fails.
+    pub fn serialize<F: Float, S: Serializer>(f: &F, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize(*f, serializer, NonFinite::Str)
+    }
+
+    /// Deserialize a float from a string using Ryū's parser.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if the input is not a string, or is a string that [`ryu::parse`]
+    /// rejects.
+    ///
+    /// [`ryu::parse`]: crate::parse
+    pub fn deserialize<'de, F: Float, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<F, D::Error> {
+        super::deserialize(deserializer, NonFinite::Str)
+    }
+
+    /// Serialize a finite float as its Ryū string, and fail on NaN and the
+    /// infinities.
+    ///
+    /// Use with `#[serde(with = "ryu::serde::as_str::finite")]`.
+    pub mod finite {
+        use super::super::NonFinite;
+        use crate::Float;
+        use serde::{Deserializer, Serializer};
+
+        /// Serialize a float as its Ryū string, or fail if it is not finite.
+        ///
+        /// ## Errors
+        ///
+        /// Fails if the float is NaN or infinite, or if `serializer` fails to
+        /// serialize the string.
+        pub fn serialize<F: Float, S: Serializer>(f: &F, serializer: S) -> Result<S::Ok, S::Error> {
+            super::super::serialize(*f, serializer, NonFinite::Error)
+        }
+
+        /// Deserialize a float from a string using Ryū's parser, or fail if it
+        /// is not finite.
+        ///
+        /// ## Errors
+        ///
+        /// Fails if the input is not a string, is a string that
+        /// [`ryu::parse`] rejects, or parses to NaN or an infinity.
+        ///
+        /// [`ryu::parse`]: crate::parse
+        pub fn deserialize<'de, F: Float, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<F, D::Error> {
+            super::super::deserialize(deserializer, NonFinite::Error)
+        }
+    }
+
+    /// Serialize a float as its Ryū string, with NaN and the infinities
+    /// written as none.
+    ///
+    /// Use with `#[serde(with = "ryu::serde::as_str::null_nonfinite")]`.
+    pub mod null_nonfinite {
+        use super::super::NonFinite;
+        use crate::Float;
+        use serde::{Deserializer, Serializer};
+
+        /// Serialize a float as its Ryū string, or as none if it is not
+        /// finite.
+        ///
+        /// ## Errors
+        ///
+        /// Returns the error from `serializer`, if serializing the string or
+        /// the none fails.
+        pub fn serialize<F: Float, S: Serializer>(f: &F, serializer: S) -> Result<S::Ok, S::Error> {
+            super::super::serialize(*f, serializer, NonFinite::Null)
+        }
+
+        /// Deserialize a float from a string using Ryū's parser, or NaN from
+        /// none.
+        ///
+        /// ## Errors
+        ///
+        /// Fails if the input is neither none nor a string, or is a string
+        /// that [`ryu::parse`] rejects.
+        ///
+        /// [`ryu::parse`]: crate::parse
+        pub fn deserialize<'de, F: Float, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<F, D::Error> {
+            super::super::deserialize(deserializer, NonFinite::Null)
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum NonFinite {
+    Str,
+    Error,
+    Null,
+}
+
+fn serialize<F: Float, S: Serializer>(
+    f: F,
+    serializer: S,
+    non_finite: NonFinite,
+) -> Result<S::Ok, S::Error> {
+    if f.is_nonfinite() {
+        match non_finite {
+            NonFinite::Str => {}
+            NonFinite::Error => {
+                return Err(ser::Error::custom(format_args!(
+                    "cannot serialize non-finite float {}",
+                    f.format_nonfinite(),
+                )));
+            }
+            NonFinite::Null => return serializer.serialize_none(),
+        }
+    }
+    let mut buffer = Buffer::new();
+    serializer.serialize_str(buffer.format(f))
+}
+
+fn deserialize<'de, F: Float, D: Deserializer<'de>>(
+    deserializer: D,
+    non_finite: NonFinite,
+) -> Result<F, D::Error> {
+    let visitor = FloatVisitor {
+        non_finite,
+        marker: PhantomData,
+    };
+    if non_finite == NonFinite::Null {
+        deserializer.deserialize_option(visitor)
+    } else {
+        deserializer.deserialize_str(visitor)
+    }
+}
+
+struct FloatVisitor<F> {
+    non_finite: NonFinite,
+    marker: PhantomData<F>,
+}
+
+impl<'de, F: Float> Visitor<'de> for FloatVisitor<F> {
+    type Value = F;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.non_finite {
+            NonFinite::Str => formatter.write_str("a floating point number as a string"),
+            NonFinite::Error => formatter.write_str("a finite floating point number as a string"),
+            NonFinite::Null => formatter.write_str("a floating point number as a string, or none"),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<F, E> {
+        match crate::parse::<F>(s.as_bytes()) {
+            Ok(f) if !(self.non_finite == NonFinite::Error && f.is_nonfinite()) => Ok(f),
+            _ => Err(E::invalid_value(Unexpected::Str(s), &self)),
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<F, E> {
+        match crate::parse::<F>(bytes) {
+            Ok(f) if !(self.non_finite == NonFinite::Error && f.is_nonfinite()) => Ok(f),
+            _ => Err(E::invalid_value(Unexpected::Bytes(bytes), &self)),
+        }
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<F, E> {
+        if self.non_finite == NonFinite::Null {
+            Ok(F::NAN)
+        } else {
+            Err(E::invalid_type(Unexpected::Option, &self))
+        }
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<F, E> {
+        if self.non_finite == NonFinite::Null {
+            Ok(F::NAN)
+        } else {
+            Err(E::invalid_type(Unexpected::Unit, &self))
+        }
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<F, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
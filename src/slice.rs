@@ -0,0 +1,300 @@
+use crate::Float;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::mem::MaybeUninit;
+use core::{ptr, slice, str};
+#[cfg(feature = "std")]
+use std::io;
+
+/// How [`format_slice`] and friends print NaN and the infinities.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NonFinite {
+    /// Print "NaN", "inf" or "-inf", the same as [`Buffer::format`].
+    ///
+    /// [`Buffer::format`]: crate::Buffer::format
+    Text,
+    /// Print "null", as in a JSON array.
+    Null,
+    /// Print nothing, leaving an empty field between the separators.
+    Empty,
+    /// Stop with [`SliceError::NonFinite`].
+    Error,
+}
+
+/// Error returned when formatting a slice of floats fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SliceError {
+    /// The element at this index is NaN or infinite, and
+    /// [`NonFinite::Error`] was requested.
+    NonFinite(usize),
+    /// The [`core::fmt::Write`] sink returned an error.
+    Fmt,
+}
+
+impl Display for SliceError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SliceError::NonFinite(index) => {
+                write!(formatter, "non-finite float at index {index}")
+            }
+            SliceError::Fmt => formatter.write_str("formatter error"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SliceError {}
+
+const NULL: &str = "null";
+
+// Size of the stack buffer that output is staged in before being handed to a
+// writer.
+const CHUNK: usize = 512;
+
+// Number of elements formatted into a `Vec` per capacity check.
+#[cfg(feature = "alloc")]
+const VEC_CHUNK: usize = 64;
+
+/// Print a slice of floating point numbers into a [`core::fmt::Write`] sink,
+/// with `separator` between consecutive numbers.
+///
+/// Each finite number is printed the same as [`Buffer::format`], and
+/// `non_finite` decides what NaN and the infinities turn into. The output is
+/// staged in a stack buffer and passed to the sink a few hundred bytes at a
+/// time. If an error is returned, the output up to the start of the failing
+/// chunk may already have been written.
+///
+/// [`Buffer::format`]: crate::Buffer::format
+///
+/// ## Example
+///
+/// ```
+/// use ryu::NonFinite;
+///
+/// let mut out = String::from("[");
+/// ryu::format_slice(&mut out, &[1.5, f64::NAN, -0.0], ",", NonFinite::Null)?;
+/// out.push(']');
+/// assert_eq!(out, "[1.5,null,-0.0]");
+/// # Ok::<(), ryu::SliceError>(())
+/// ```
+///
+/// ## Errors
+///
+/// Returns [`SliceError::NonFinite`] with the index of the first NaN or
+/// infinity if `non_finite` is [`NonFinite::Error`], and [`SliceError::Fmt`]
+/// if the sink returns an error.
+pub fn format_slice<W, F>(
+    writer: &mut W,
+    floats: &[F],
+    separator: &str,
+    non_finite: NonFinite,
+) -> Result<(), SliceError>
+where
+    W: fmt::Write + ?Sized,
+    F: Float,
+{
+    chunked(floats, separator, non_finite, |bytes| {
+        // Safety: everything written into a chunk is ASCII or a whole copy of
+        // the separator.
+        let s = unsafe { str::from_utf8_unchecked(bytes) };
+        writer.write_str(s).map_err(|fmt::Error| SliceError::Fmt)
+    })
+}
+
+/// Print a slice of floating point numbers into a [`std::io::Write`] sink,
+/// with `separator` between consecutive numbers.
+///
+/// This is the same as [`format_slice`], for an `io::Write`. A
+/// [`NonFinite::Error`] failure is reported as an [`io::Error`] of kind
+/// `InvalidData` that wraps the [`SliceError`].
+///
+/// ## Example
+///
+/// ```
+/// use ryu::NonFinite;
+///
+/// let mut out = Vec::new();
+/// ryu::format_slice_io(&mut out, &[1.0f32, 0.3], "\t", NonFinite::Error)?;
+/// out.push(b'\n');
+/// assert_eq!(out, b"1.0\t0.3\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// ## Errors
+///
+/// Returns the error from the sink if writing to it fails, or an error of kind
+/// `InvalidData` for the first NaN or infinity if `non_finite` is
+/// [`NonFinite::Error`].
+#[cfg(feature = "std")]
+pub fn format_slice_io<W, F>(
+    writer: &mut W,
+    floats: &[F],
+    separator: &str,
+    non_finite: NonFinite,
+) -> io::Result<()>
+where
+    W: io::Write + ?Sized,
+    F: Float,
+{
+    let mut io_error = None;
+    let result = chunked(floats, separator, non_finite, |bytes| {
+        writer.write_all(bytes).map_err(|error| {
+            io_error = Some(error);
+            SliceError::Fmt
+        })
+    });
+    match (result, io_error) {
+        (Ok(()), _) => Ok(()),
+        (Err(_), Some(error)) => Err(error),
+        (Err(error), None) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+    }
+}
+
+/// Print a slice of floating point numbers onto the end of a `Vec<u8>`, with
+/// `separator` between consecutive numbers.
+///
+/// This is the same as [`format_slice`], for a `Vec<u8>`. Capacity is
+/// reserved for a run of numbers at a time and each number is printed directly
+/// into the vector's spare capacity. If an error is returned, the vector is
+/// left as it was.
+///
+/// ## Example
+///
+/// ```
+/// use ryu::{NonFinite, SliceError};
+///
+/// let mut out = b"x,".to_vec();
+/// ryu::append_slice(&mut out, &[1e-7, f64::INFINITY], ",", NonFinite::Empty)?;
+/// assert_eq!(out, b"x,1e-7,");
+///
+/// let result = ryu::append_slice(&mut out, &[1.0, f64::NAN], ",", NonFinite::Error);
+/// assert_eq!(result, Err(SliceError::NonFinite(1)));
+/// assert_eq!(out, b"x,1e-7,");
+/// # Ok::<(), SliceError>(())
+/// ```
+///
+/// ## Errors
+///
+/// Returns [`SliceError::NonFinite`] with the index of the first NaN or
+/// infinity if `non_finite` is [`NonFinite::Error`]. This is the only error,
+/// since writing into a vector cannot fail.
+#[cfg(feature = "alloc")]
+pub fn append_slice<F: Float>(
+    vec: &mut Vec<u8>,
+    floats: &[F],
+    separator: &str,
+    non_finite: NonFinite,
+) -> Result<(), SliceError> {
+    let original_len = vec.len();
+    let max_len = F::MAX_LEN + separator.len();
+    for (i, chunk) in floats.chunks(VEC_CHUNK).enumerate() {
+        vec.reserve(chunk.len() * max_len);
+        let len = vec.len();
+        let result = unsafe {
+            fill(
+                chunk,
+                i * VEC_CHUNK,
+                separator,
+                non_finite,
+                vec.as_mut_ptr().add(len),
+            )
+        };
+        match result {
+            // Safety: fill wrote n initialized bytes into the spare capacity.
+            Ok(n) => unsafe { vec.set_len(len + n) },
+            Err(error) => {
+                vec.truncate(original_len);
+                return Err(error);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Stages the output in a stack buffer, passing it to `flush` whenever the next
+// run of numbers might not fit.
+fn chunked<F: Float>(
+    floats: &[F],
+    separator: &str,
+    non_finite: NonFinite,
+    mut flush: impl FnMut(&[u8]) -> Result<(), SliceError>,
+) -> Result<(), SliceError> {
+    let max_len = F::MAX_LEN + separator.len();
+    if max_len > CHUNK {
+        // A separator this long is written directly instead of staged.
+        let mut buffer = crate::Buffer::new();
+        for (i, &f) in floats.iter().enumerate() {
+            if i != 0 {
+                flush(separator.as_bytes())?;
+            }
+            flush(element(f, i, non_finite, &mut buffer)?.as_bytes())?;
+        }
+        return Ok(());
+    }
+
+    let mut bytes = [MaybeUninit::<u8>::uninit(); CHUNK];
+    let per_chunk = CHUNK / max_len;
+    for (i, chunk) in floats.chunks(per_chunk).enumerate() {
+        unsafe {
+            let result = bytes.as_mut_ptr().cast::<u8>();
+            let n = fill(chunk, i * per_chunk, separator, non_finite, result)?;
+            flush(slice::from_raw_parts(result, n))?;
+        }
+    }
+    Ok(())
+}
+
+// Prints the numbers in `chunk`, which starts at index `first` of the whole
+// slice, each preceded by the separator unless it is the very first one.
+// Requires space for `chunk.len() * (F::MAX_LEN + separator.len())` bytes.
+unsafe fn fill<F: Float>(
+    chunk: &[F],
+    first: usize,
+    separator: &str,
+    non_finite: NonFinite,
+    result: *mut u8,
+) -> Result<usize, SliceError> {
+    let mut index = 0;
+    for (i, &f) in chunk.iter().enumerate() {
+        if first + i != 0 {
+            ptr::copy_nonoverlapping(separator.as_ptr(), result.add(index), separator.len());
+            index += separator.len();
+        }
+        index += if f.is_nonfinite() {
+            let s = nonfinite(f, first + i, non_finite)?;
+            ptr::copy_nonoverlapping(s.as_ptr(), result.add(index), s.len());
+            s.len()
+        } else {
+            f.write_to_ryu_buffer(result.add(index))
+        };
+    }
+    Ok(index)
+}
+
+fn element<F: Float>(
+    f: F,
+    index: usize,
+    non_finite: NonFinite,
+    buffer: &mut crate::Buffer,
+) -> Result<&str, SliceError> {
+    if f.is_nonfinite() {
+        nonfinite(f, index, non_finite)
+    } else {
+        Ok(buffer.format_finite(f))
+    }
+}
+
+#[cold]
+fn nonfinite<F: Float>(
+    f: F,
+    index: usize,
+    non_finite: NonFinite,
+) -> Result<&'static str, SliceError> {
+    match non_finite {
+        NonFinite::Text => Ok(f.format_nonfinite()),
+        NonFinite::Null => Ok(NULL),
+        NonFinite::Empty => Ok(""),
+        NonFinite::Error => Err(SliceError::NonFinite(index)),
+    }
+}
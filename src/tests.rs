@@ -1,11 +1,31 @@
 use crate::digit_table;
 use core::mem;
 
+// Total size in bytes of the static tables, for each combination of features
+// that affects it:
+//
+//                 default    small     tiny
+//     ryu           11104     1212      200
+//     schubfach     21592    11700      200
+//
+// The Schubfach backend only replaces the conversion to decimal. Parsing still
+// uses Ryū's powers of 5, so its tables come on top of those, except with
+// "tiny" where neither has any. Parsing also has 184 bytes of exact powers of
+// ten, which "tiny" computes instead.
 const _: () = {
+    #[allow(unused_mut)]
     let mut static_data = mem::size_of_val(&digit_table::DIGIT_TABLE);
 
-    #[cfg(feature = "small")]
+    #[cfg(not(feature = "tiny"))]
     {
+        static_data += mem::size_of_val(&crate::s2d::DOUBLE_POW10);
+    }
+
+    #[cfg(feature = "tiny")]
+    let expected = 200;
+
+    #[cfg(all(feature = "small", not(feature = "tiny")))]
+    let expected = {
         use crate::d2s_small_table;
 
         static_data += mem::size_of_val(&d2s_small_table::DOUBLE_POW5_INV_SPLIT2)
@@ -14,16 +34,28 @@ const _: () = {
             + mem::size_of_val(&d2s_small_table::POW5_OFFSETS)
             + mem::size_of_val(&d2s_small_table::DOUBLE_POW5_TABLE);
 
-        assert!(static_data == 1016);
-    }
+        1212
+    };
 
-    #[cfg(not(feature = "small"))]
-    {
+    #[cfg(not(any(feature = "small", feature = "tiny")))]
+    let expected = {
         use crate::d2s_full_table;
 
         static_data += mem::size_of_val(&d2s_full_table::DOUBLE_POW5_INV_SPLIT)
             + mem::size_of_val(&d2s_full_table::DOUBLE_POW5_SPLIT);
 
-        assert!(static_data == 10888); // 10.6K
-    }
+        11104 // 10.8K
+    };
+
+    #[cfg(all(feature = "schubfach", not(feature = "tiny")))]
+    let expected = {
+        use crate::schubfach;
+
+        static_data += mem::size_of_val(&schubfach::POW10_SIGNIFICANDS64)
+            + mem::size_of_val(&schubfach::POW10_SIGNIFICANDS32);
+
+        expected + 10488
+    };
+
+    assert!(static_data == expected);
 };
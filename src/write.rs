@@ -0,0 +1,232 @@
+use crate::{Buffer, Float};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::{fmt, ptr};
+#[cfg(feature = "std")]
+use std::io;
+
+/// Print a floating point number into a [`core::fmt::Write`] sink.
+///
+/// The output is the same as [`Buffer::format`], including "NaN", "inf" and
+/// "-inf" for the special cases, and is passed to the sink in a single
+/// `write_str` call.
+///
+/// ## Example
+///
+/// ```
+/// use std::fmt::Write;
+///
+/// let mut out = String::new();
+/// out.push('[');
+/// ryu::write_fmt(&mut out, 1.234)?;
+/// out.push(']');
+/// assert_eq!(out, "[1.234]");
+/// # Ok::<(), std::fmt::Error>(())
+/// ```
+///
+/// ## Errors
+///
+/// Returns the error from `writer`, if writing to it fails.
+#[inline]
+pub fn write_fmt<W, F>(writer: &mut W, f: F) -> fmt::Result
+where
+    W: fmt::Write + ?Sized,
+    F: Float,
+{
+    let mut buffer = Buffer::new();
+    writer.write_str(buffer.format(f))
+}
+
+/// Print a floating point number into a [`std::io::Write`] sink.
+///
+/// The output is the same as [`Buffer::format`], including "NaN", "inf" and
+/// "-inf" for the special cases, and is passed to the sink in a single
+/// `write_all` call.
+///
+/// ## Example
+///
+/// ```
+/// let mut out = Vec::new();
+/// ryu::write_io(&mut out, 1.234)?;
+/// assert_eq!(out, b"1.234");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// ## Errors
+///
+/// Returns the error from `writer`'s `write_all`, if writing to it fails.
+#[cfg(feature = "std")]
+#[inline]
+pub fn write_io<W, F>(writer: &mut W, f: F) -> io::Result<()>
+where
+    W: io::Write + ?Sized,
+    F: Float,
+{
+    let mut buffer = Buffer::new();
+    writer.write_all(buffer.format(f).as_bytes())
+}
+
+/// Print a floating point number into the beginning of a byte slice and return
+/// the number of bytes written, or `None` if the slice is too short to hold the
+/// output.
+///
+/// The output is the same as [`Buffer::format`], including "NaN", "inf" and
+/// "-inf" for the special cases. Nothing is written if `None` is returned.
+///
+/// A slice of at least 24 bytes (16 bytes for f32) always has enough space; in
+/// that case the number is printed directly into the slice.
+///
+/// ## Example
+///
+/// ```
+/// let mut out = *b"x=________";
+/// let n = ryu::format_into(1.5f64, &mut out[2..]).unwrap();
+/// assert_eq!(&out[..2 + n], b"x=1.5");
+///
+/// assert_eq!(ryu::format_into(1.2345f64, &mut out[8..]), None);
+/// ```
+#[inline]
+pub fn format_into<F: Float>(f: F, out: &mut [u8]) -> Option<usize> {
+    // Safety: MaybeUninit<u8> has the same layout as u8, and only initialized
+    // bytes are ever written through the reborrowed slice.
+    let out = unsafe { &mut *(out as *mut [u8] as *mut [MaybeUninit<u8>]) };
+    format_into_uninit(f, out)
+}
+
+/// Print a floating point number into the beginning of a possibly
+/// uninitialized byte slice and return the number of bytes written, or `None`
+/// if the slice is too short to hold the output.
+///
+/// On success the first `n` elements of `out` are initialized, where `n` is the
+/// returned length. This is otherwise the same as [`format_into`].
+///
+/// ## Example
+///
+/// ```
+/// use std::mem::MaybeUninit;
+///
+/// let mut vec = Vec::<u8>::with_capacity(64);
+/// let n = ryu::format_into_uninit(0.3f64, vec.spare_capacity_mut()).unwrap();
+/// unsafe { vec.set_len(n) };
+/// assert_eq!(vec, b"0.3");
+/// ```
+#[inline]
+pub fn format_into_uninit<F: Float>(f: F, out: &mut [MaybeUninit<u8>]) -> Option<usize> {
+    if f.is_nonfinite() {
+        return copy_into(f.format_nonfinite(), out);
+    }
+    if out.len() >= F::MAX_LEN {
+        unsafe { Some(f.write_to_ryu_buffer(out.as_mut_ptr().cast::<u8>())) }
+    } else {
+        let mut buffer = Buffer::new();
+        copy_into(buffer.format_finite(f), out)
+    }
+}
+
+/// Print a floating point number into the beginning of a byte array that is
+/// statically known to be large enough, and return the number of bytes
+/// written.
+///
+/// The array must have at least 24 elements for f64 or 16 for f32; smaller
+/// arrays are rejected at compile time. The output is the same as
+/// [`Buffer::format`].
+///
+/// ## Example
+///
+/// ```
+/// let mut out = [0u8; 16];
+/// let n = ryu::format_into_array(1.234f32, &mut out);
+/// assert_eq!(&out[..n], b"1.234");
+/// ```
+///
+/// ```compile_fail
+/// let mut out = [0u8; 16];
+/// let n = ryu::format_into_array(1.234f64, &mut out);
+/// ```
+#[inline]
+pub fn format_into_array<F: Float, const N: usize>(f: F, out: &mut [u8; N]) -> usize {
+    #[allow(clippy::let_unit_value)]
+    let () = AssertCapacity::<F, N>::OK;
+    if f.is_nonfinite() {
+        let s = f.format_nonfinite();
+        out[..s.len()].copy_from_slice(s.as_bytes());
+        s.len()
+    } else {
+        unsafe { f.write_to_ryu_buffer(out.as_mut_ptr()) }
+    }
+}
+
+/// Print a floating point number onto the end of a `Vec<u8>`.
+///
+/// The output is the same as [`Buffer::format`], including "NaN", "inf" and
+/// "-inf" for the special cases. Enough capacity for the longest possible
+/// output is reserved up front and the number is printed directly into the
+/// vector's spare capacity, without going through an intermediate buffer.
+///
+/// ## Example
+///
+/// ```
+/// let mut out = b"[".to_vec();
+/// ryu::append(&mut out, 1.5f64);
+/// out.push(b',');
+/// ryu::append(&mut out, f32::NAN);
+/// out.push(b']');
+/// assert_eq!(out, b"[1.5,NaN]");
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn append<F: Float>(vec: &mut Vec<u8>, f: F) {
+    vec.reserve(F::MAX_LEN);
+    let len = vec.len();
+    unsafe {
+        let result = vec.as_mut_ptr().add(len);
+        let n = if f.is_nonfinite() {
+            let s = f.format_nonfinite();
+            ptr::copy_nonoverlapping(s.as_ptr(), result, s.len());
+            s.len()
+        } else {
+            f.write_to_ryu_buffer(result)
+        };
+        debug_assert!(n <= F::MAX_LEN);
+        vec.set_len(len + n);
+    }
+}
+
+/// Print a floating point number onto the end of a `String`.
+///
+/// This is the same as [`append`], for a `String`.
+///
+/// ## Example
+///
+/// ```
+/// let mut out = String::from("x=");
+/// ryu::append_string(&mut out, -0.0f64);
+/// assert_eq!(out, "x=-0.0");
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn append_string<F: Float>(string: &mut String, f: F) {
+    // Safety: the appended bytes are ASCII.
+    unsafe { append(string.as_mut_vec(), f) }
+}
+
+struct AssertCapacity<F, const N: usize>(PhantomData<F>);
+
+impl<F: Float, const N: usize> AssertCapacity<F, N> {
+    const OK: () = assert!(N >= F::MAX_LEN, "array is too small to hold the output");
+}
+
+#[inline]
+fn copy_into(s: &str, out: &mut [MaybeUninit<u8>]) -> Option<usize> {
+    if s.len() > out.len() {
+        return None;
+    }
+    unsafe {
+        ptr::copy_nonoverlapping(s.as_ptr(), out.as_mut_ptr().cast::<u8>(), s.len());
+    }
+    Some(s.len())
+}
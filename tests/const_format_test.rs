@@ -0,0 +1,94 @@
+#![allow(clippy::cast_possible_truncation, clippy::unreadable_literal)]
+
+use rand::RngExt as _;
+
+const EXAMPLES: [&str; 12] = [
+    ryu::const_format!(1.5e-3),
+    ryu::const_format!(-0.0),
+    ryu::const_format!(1.0),
+    ryu::const_format!(123456789.0),
+    ryu::const_format!(1e16),
+    ryu::const_format!(0.0001),
+    ryu::const_format!(1.234e-300),
+    ryu::const_format!(f64::MAX),
+    ryu::const_format!(f64::MIN_POSITIVE),
+    ryu::const_format!(5e-324),
+    ryu::const_format!(f64::NAN),
+    ryu::const_format!(f64::NEG_INFINITY),
+];
+
+const EXAMPLES32: [&str; 8] = [
+    ryu::const_format!(0.3f32),
+    ryu::const_format!(-1.5e-10f32),
+    ryu::const_format!(1e13f32),
+    ryu::const_format!(0.00001f32),
+    ryu::const_format!(f32::MAX),
+    ryu::const_format!(1e-45f32),
+    ryu::const_format!(f32::INFINITY),
+    ryu::const_format!(0.0f32),
+];
+
+#[test]
+fn test_const_format() {
+    assert_eq!(
+        EXAMPLES,
+        [
+            "0.0015",
+            "-0.0",
+            "1.0",
+            "123456789.0",
+            "1e16",
+            "0.0001",
+            "1.234e-300",
+            "1.7976931348623157e308",
+            "2.2250738585072014e-308",
+            "5e-324",
+            "NaN",
+            "-inf",
+        ],
+    );
+    assert_eq!(
+        EXAMPLES32,
+        [
+            "0.3",
+            "-1.5e-10",
+            "1e13",
+            "0.00001",
+            "3.4028235e38",
+            "1e-45",
+            "inf",
+            "0.0",
+        ],
+    );
+}
+
+#[test]
+fn test_matches_buffer() {
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut rng = rand::rng();
+    let mut buffer = ryu::Buffer::new();
+    for _ in 0..n {
+        let f = f64::from_bits(rng.random());
+        let (bytes, len) = ryu::format_const(f);
+        assert_eq!(&bytes[..len], buffer.format(f).as_bytes());
+
+        let f = f32::from_bits(rng.random());
+        let (bytes, len) = ryu::format_const(f);
+        assert_eq!(&bytes[..len], buffer.format(f).as_bytes());
+    }
+}
+
+#[test]
+fn test_every_layout() {
+    let mut buffer = ryu::Buffer::new();
+    let mut f = 1.2345678901234567e-20f64;
+    while f < 1e25 {
+        for g in [f, -f, f.trunc(), f64::from(f as f32)] {
+            let (bytes, len) = ryu::format_const(g);
+            assert_eq!(&bytes[..len], buffer.format(g).as_bytes());
+            let (bytes, len) = ryu::format_const(g as f32);
+            assert_eq!(&bytes[..len], buffer.format(g as f32).as_bytes());
+        }
+        f *= 10.0;
+    }
+}
@@ -44,6 +44,120 @@ mod d2s_small_table;
 
 use d2s_full_table::{DOUBLE_POW5_INV_SPLIT, DOUBLE_POW5_SPLIT};
 use d2s_small_table::{compute_inv_pow5, compute_pow5};
+use std::cmp::Ordering;
+
+// A deliberately simple arbitrary precision integer, little-endian in 32-bit
+// limbs, to check the generated tables against.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Big(Vec<u32>);
+
+impl Big {
+    fn from_u128(mut n: u128) -> Self {
+        let mut limbs = Vec::new();
+        while n != 0 {
+            limbs.push(n as u32);
+            n >>= 32;
+        }
+        Big(limbs)
+    }
+
+    fn pow5(i: u32) -> Self {
+        let mut big = Big::from_u128(1);
+        for _ in 0..i {
+            big = big.mul(&Big::from_u128(5));
+        }
+        big
+    }
+
+    fn pow2(i: u32) -> Self {
+        let mut limbs = vec![0; i as usize / 32];
+        limbs.push(1 << (i % 32));
+        Big(limbs)
+    }
+
+    fn bits(&self) -> u32 {
+        match self.0.last() {
+            Some(top) => 32 * self.0.len() as u32 - top.leading_zeros(),
+            None => 0,
+        }
+    }
+
+    fn mul(&self, other: &Big) -> Big {
+        let mut limbs = vec![0u32; self.0.len() + other.0.len()];
+        for (i, &a) in self.0.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.0.iter().enumerate() {
+                let sum = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            limbs[i + other.0.len()] = carry as u32;
+        }
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Big(limbs)
+    }
+}
+
+impl PartialOrd for Big {
+    fn partial_cmp(&self, other: &Big) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Big {
+    fn cmp(&self, other: &Big) -> Ordering {
+        let by_len = self.0.len().cmp(&other.0.len());
+        by_len.then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
+
+fn split(entry: (u64, u64)) -> u128 {
+    entry.0 as u128 | (entry.1 as u128) << 64
+}
+
+#[test]
+fn test_pow5_split() {
+    assert_eq!(DOUBLE_POW5_SPLIT.len(), 326);
+    for (i, &entry) in DOUBLE_POW5_SPLIT.iter().enumerate() {
+        // entry * 2^(bits - 125) <= 5^i < (entry + 1) * 2^(bits - 125)
+        let pow5 = Big::pow5(i as u32);
+        let bits = pow5.bits();
+        assert_eq!(bits, common::pow5bits(i as i32) as u32, "entry {}", i);
+        let value = split(entry);
+        assert_eq!(128 - value.leading_zeros(), 125, "entry {}", i);
+        let (scale_entry, scale_pow5) = if bits >= 125 {
+            (Big::pow2(bits - 125), Big::pow2(0))
+        } else {
+            (Big::pow2(0), Big::pow2(125 - bits))
+        };
+        let pow5 = pow5.mul(&scale_pow5);
+        assert!(
+            Big::from_u128(value).mul(&scale_entry) <= pow5,
+            "entry {}",
+            i
+        );
+        assert!(
+            pow5 < Big::from_u128(value + 1).mul(&scale_entry),
+            "entry {}",
+            i
+        );
+    }
+}
+
+#[test]
+fn test_pow5_inv_split() {
+    assert_eq!(DOUBLE_POW5_INV_SPLIT.len(), 344);
+    for (i, &entry) in DOUBLE_POW5_INV_SPLIT.iter().enumerate() {
+        // entry - 1 == floor(2^(pow5bits(i) - 1 + 125) / 5^i)
+        let pow5 = Big::pow5(i as u32);
+        let pow2 = Big::pow2(common::pow5bits(i as i32) as u32 - 1 + 125);
+        let value = split(entry);
+        assert!(Big::from_u128(value - 1).mul(&pow5) <= pow2, "entry {}", i);
+        assert!(pow2 < Big::from_u128(value).mul(&pow5), "entry {}", i);
+    }
+}
 
 #[test]
 fn test_compute_pow5() {
@@ -54,7 +168,36 @@ fn test_compute_pow5() {
 
 #[test]
 fn test_compute_inv_pow5() {
-    for (i, entry) in DOUBLE_POW5_INV_SPLIT[..292].iter().enumerate() {
+    for (i, entry) in DOUBLE_POW5_INV_SPLIT.iter().enumerate() {
         assert_eq!(*entry, unsafe { compute_inv_pow5(i as u32) }, "entry {}", i);
     }
 }
+
+#[test]
+fn test_compute_inv_pow5_parse_range() {
+    // d2s needs inverse powers up to 5^291 only, but s2d divides by 5^-e10
+    // for exponents down to -343, past the 304 powers the original 19 words
+    // of offsets covered.
+    assert!(d2s_small_table::POW5_INV_OFFSETS.len() * 16 >= DOUBLE_POW5_INV_SPLIT.len());
+    for (i, entry) in DOUBLE_POW5_INV_SPLIT.iter().enumerate().skip(292) {
+        assert_eq!(*entry, unsafe { compute_inv_pow5(i as u32) }, "entry {}", i);
+    }
+}
+
+#[cfg(feature = "tiny")]
+#[test]
+fn test_runtime_pow5() {
+    for (i, entry) in DOUBLE_POW5_SPLIT.iter().enumerate() {
+        let computed = unsafe { d2s_intrinsics::compute_pow5(i as u32) };
+        assert_eq!(*entry, computed, "entry {}", i);
+    }
+}
+
+#[cfg(feature = "tiny")]
+#[test]
+fn test_runtime_inv_pow5() {
+    for (i, entry) in DOUBLE_POW5_INV_SPLIT.iter().enumerate() {
+        let computed = unsafe { d2s_intrinsics::compute_inv_pow5(i as u32) };
+        assert_eq!(*entry, computed, "entry {}", i);
+    }
+}
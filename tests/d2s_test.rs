@@ -21,6 +21,7 @@
 #![allow(
     clippy::approx_constant,
     clippy::cast_lossless,
+    clippy::cast_precision_loss,
     clippy::float_cmp,
     clippy::int_plus_one,
     clippy::non_ascii_literal,
@@ -329,3 +330,16 @@ fn test_small_integers() {
     check!(549755813888000.0);
     check!(8796093022208000.0);
 }
+
+#[test]
+fn test_random_integers() {
+    let n = if cfg!(miri) { 100 } else { 1000000 };
+    let mut buffer = ryu::Buffer::new();
+    for _ in 0..n {
+        // Up to 53 bits, uniform over the number of bits so that small
+        // integers are common.
+        let i = rand::random::<u64>() >> rand::random_range(11..64);
+        assert_eq!(buffer.format(i as f64), format!("{i}.0"));
+        assert_eq!(buffer.format(-(i as f64)), format!("-{i}.0"));
+    }
+}
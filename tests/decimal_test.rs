@@ -0,0 +1,241 @@
+#![allow(
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::unreadable_literal
+)]
+
+use rand::RngExt as _;
+use ryu::Rounding;
+use std::cmp::Ordering;
+use std::fmt::{Debug, LowerExp};
+use std::str::FromStr;
+
+fn check<F: ryu::Float + Copy>(f: F) {
+    let mut buffer = ryu::Buffer::new();
+    let expected = buffer.format(f).to_owned();
+    assert_eq!(ryu::formatted_len(f), expected.len(), "{expected}");
+
+    let decimal = ryu::Decimal::new(f);
+    assert_eq!(decimal.formatted_len(), expected.len(), "{expected}");
+    assert_eq!(buffer.format_decimal(&decimal), expected);
+
+    let mut out = vec![b'_'; decimal.formatted_len() + 1];
+    assert_eq!(decimal.write_into(&mut out[..expected.len() - 1]), None);
+    assert_eq!(decimal.write_into(&mut out), Some(expected.len()));
+    assert_eq!(&out[..expected.len()], expected.as_bytes());
+    assert_eq!(out[expected.len()], b'_');
+}
+
+#[test]
+fn test_decomposition() {
+    let decimal = ryu::Decimal::new(1.234e-7f64);
+    assert_eq!((decimal.mantissa(), decimal.exponent()), (1234, -10));
+    assert!(!decimal.is_sign_negative());
+
+    let decimal = ryu::Decimal::new(-1e30f32);
+    assert_eq!((decimal.mantissa(), decimal.exponent()), (1, 30));
+    assert!(decimal.is_sign_negative());
+
+    let decimal = ryu::Decimal::new(-0.0f64);
+    assert_eq!((decimal.mantissa(), decimal.exponent()), (0, 0));
+    assert!(decimal.is_sign_negative());
+
+    let decimal = ryu::Decimal::new(f64::NEG_INFINITY);
+    assert_eq!((decimal.mantissa(), decimal.exponent()), (0, 0));
+    assert!(decimal.is_sign_negative());
+    assert!(!ryu::Decimal::new(f32::NAN).is_sign_negative());
+}
+
+#[test]
+fn test_special() {
+    for f in [0.0, -0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        check(f);
+        check(f as f32);
+    }
+}
+
+#[test]
+fn test_layouts() {
+    let mut f = 5e-324f64;
+    while f.is_finite() {
+        check(f);
+        check(-f);
+        check(f * 1.2345678901234567);
+        f *= 10.0;
+    }
+    let mut f = 1e-45f32;
+    while f.is_finite() {
+        check(f);
+        check(-f);
+        check(f * 1.2345678);
+        f *= 10.0;
+    }
+    check(2.2250738585072014e-308f64);
+    check(f64::MAX);
+    check(f32::MAX);
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        check(f64::from_bits(rng.random()));
+        check(f32::from_bits(rng.random()));
+    }
+}
+
+// Significant digits and scientific exponent of a finite nonzero magnitude.
+fn normalize(scientific: &str) -> (String, i32) {
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    (
+        digits.trim_end_matches('0').to_owned(),
+        exponent.parse().unwrap(),
+    )
+}
+
+fn check_rounded<F>(f: F)
+where
+    F: ryu::Float + Copy + PartialEq + Debug + LowerExp + FromStr,
+    F::Err: Debug,
+{
+    let mut buffer = ryu::Buffer::new();
+    let nearest = buffer.format(f).to_owned();
+    assert_eq!(buffer.format_rounded(f, Rounding::NearestEven), nearest);
+
+    for rounding in [Rounding::TowardZero, Rounding::Upward, Rounding::Downward] {
+        let decimal = ryu::Decimal::new_rounded(f, rounding);
+        let printed = buffer.format_rounded(f, rounding).to_owned();
+        assert_eq!(decimal.formatted_len(), printed.len(), "{printed}");
+        assert_eq!(buffer.format_decimal(&decimal), printed);
+        let mantissa = decimal.mantissa();
+        let exponent = decimal.exponent();
+        let negative = decimal.is_sign_negative();
+        assert_eq!(
+            ryu::from_decimal::<F>(mantissa, exponent, negative),
+            printed.parse::<F>().unwrap()
+        );
+        // Reading back in the opposite direction lands on f, even for the
+        // 18-digit mantissas that ryu::parse rejects.
+        let readback = match rounding {
+            Rounding::TowardZero if negative => Rounding::Downward,
+            Rounding::TowardZero | Rounding::Downward => Rounding::Upward,
+            Rounding::Upward => Rounding::Downward,
+            Rounding::NearestEven => unreachable!(),
+        };
+        assert_eq!(ryu::parse_rounded::<F>(printed.as_bytes(), readback), Ok(f));
+
+        let digits = mantissa.to_string();
+        let exact = normalize(format!("{f:.1100e}").trim_start_matches('-'));
+        let printed_exact = (
+            digits.trim_end_matches('0').to_owned(),
+            exponent + digits.len() as i32 - 1,
+        );
+        let magnitude_order = (printed_exact.1, &printed_exact.0).cmp(&(exact.1, &exact.0));
+        let order = if negative {
+            magnitude_order.reverse()
+        } else {
+            magnitude_order
+        };
+        match rounding {
+            Rounding::TowardZero => assert_ne!(magnitude_order, Ordering::Greater, "{printed}"),
+            Rounding::Upward => assert_ne!(order, Ordering::Less, "{printed}"),
+            Rounding::Downward => assert_ne!(order, Ordering::Greater, "{printed}"),
+            Rounding::NearestEven => unreachable!(),
+        }
+        // Only one side of the rounding interval to choose from.
+        let nearest_digits = ryu::Decimal::new(f).mantissa().to_string();
+        assert!(digits.len() >= nearest_digits.len(), "{printed}");
+
+        assert_eq!(printed.parse::<F>().unwrap(), f, "{printed}");
+        // One digit fewer on the same side no longer reads back as f.
+        let round_up =
+            rounding == Rounding::Upward && !negative || rounding == Rounding::Downward && negative;
+        let shorter = mantissa / 10 + round_up as u64;
+        assert_ne!(
+            ryu::from_decimal::<F>(shorter, exponent + 1, negative),
+            f,
+            "{printed}"
+        );
+    }
+}
+
+#[test]
+fn test_rounded() {
+    let mut buffer = ryu::Buffer::new();
+    assert_eq!(buffer.format_rounded(0.1, Rounding::Downward), "0.1");
+    assert_eq!(
+        buffer.format_rounded(0.1, Rounding::Upward),
+        "0.10000000000000001"
+    );
+    assert_eq!(buffer.format_rounded(-0.1, Rounding::Upward), "-0.1");
+    assert_eq!(
+        buffer.format_rounded(-0.1, Rounding::Downward),
+        "-0.10000000000000001"
+    );
+    assert_eq!(buffer.format_rounded(0.3, Rounding::Upward), "0.3");
+    assert_eq!(
+        buffer.format_rounded(0.3, Rounding::Downward),
+        "0.29999999999999998"
+    );
+    assert_eq!(buffer.format_rounded(0.1f32, Rounding::TowardZero), "0.1");
+    assert_eq!(buffer.format_rounded(1.5, Rounding::Downward), "1.5");
+    assert_eq!(buffer.format_rounded(-0.0, Rounding::Upward), "-0.0");
+    assert_eq!(buffer.format_rounded(f64::NAN, Rounding::Upward), "NaN");
+    assert_eq!(
+        buffer.format_rounded(f64::MAX, Rounding::Upward),
+        "1.7976931348623158e308"
+    );
+    assert_eq!(
+        buffer.format_rounded(f64::MAX, Rounding::Downward),
+        "1.7976931348623157e308"
+    );
+    assert_eq!(buffer.format_rounded(5e-324, Rounding::Downward), "4e-324");
+    assert_eq!(buffer.format_rounded(5e-324, Rounding::Upward), "5e-324");
+    // One more digit than format ever prints.
+    assert_eq!(
+        buffer.format_rounded(1.0715660391465826e-75, Rounding::Downward),
+        "1.07156603914658258e-75"
+    );
+    assert_eq!(
+        buffer.format_rounded(-1.1060212804019674e-299, Rounding::Upward),
+        "-1.10602128040196739e-299"
+    );
+
+    for f in [
+        5e-324,
+        1e-323,
+        1.0,
+        0.3,
+        2.2250738585072014e-308,
+        9007199254740993.0,
+        1.0715660391465826e-75,
+        1.1060212804019674e-299,
+        f64::MAX,
+    ] {
+        check_rounded(f);
+        check_rounded(-f);
+    }
+    for f in [1e-45, 3e-45, 1.0, 0.3, 1.1754944e-38, 16777217.0, f32::MAX] {
+        check_rounded(f);
+        check_rounded(-f);
+    }
+}
+
+#[test]
+fn test_rounded_random() {
+    let n = if cfg!(miri) { 100 } else { 5000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let f = f64::from_bits(rng.random());
+        if f.is_finite() && f != 0.0 {
+            check_rounded(f);
+        }
+        let f = f32::from_bits(rng.random());
+        if f.is_finite() && f != 0.0 {
+            check_rounded(f);
+        }
+    }
+}
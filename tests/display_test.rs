@@ -0,0 +1,35 @@
+#![allow(clippy::cast_possible_truncation)]
+
+use ryu::Display;
+
+// For these values Ryū and the standard library print the same digits, so the
+// standard library's handling of each flag is the expected output.
+macro_rules! check {
+    ($($spec:literal),*) => {
+        for f in [1.5, -1.5, 0.25, -1234.5, f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            $(
+                assert_eq!(format!($spec, Display(f)), format!($spec, f));
+                assert_eq!(format!($spec, Display(f as f32)), format!($spec, f as f32));
+            )*
+        }
+    };
+}
+
+#[test]
+fn test_matches_std() {
+    check!(
+        "{}", "{:?}", "{:8}", "{:<8}", "{:^8}", "{:>8}", "{:*^9}", "{:_<3}", "{:+}", "{:+8}",
+        "{:<+8}", "{:08}", "{:+08}", "{:<08}", "{:02}", "{:#>+10}", "{:.3}", "{:+.1}", "{:010.2}"
+    );
+}
+
+#[test]
+fn test_ryu_digits() {
+    assert_eq!(format!("{}", Display(1.0)), "1.0");
+    assert_eq!(format!("{}", Display(-0.0)), "-0.0");
+    assert_eq!(format!("{}", Display(1e30)), "1e30");
+    assert_eq!(format!("{:?}", Display(1.1e-64)), "1.1e-64");
+    assert_eq!(format!("[{:>10}]", Display(1e30f32)), "[      1e30]");
+    assert_eq!(format!("[{:+010}]", Display(-1e-7)), "[-000001e-7]");
+    assert_eq!(format!("[{:+}]", Display(0.0)), "[+0.0]");
+}
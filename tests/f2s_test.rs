@@ -20,6 +20,7 @@
 
 #![allow(
     clippy::approx_constant,
+    clippy::cast_precision_loss,
     clippy::float_cmp,
     clippy::non_ascii_literal,
     clippy::unreadable_literal,
@@ -179,3 +180,15 @@ fn test_output_length() {
     check!(1.2345678);
     check!(1.23456735e-36);
 }
+
+#[test]
+fn test_random_integers() {
+    let n = if cfg!(miri) { 100 } else { 1000000 };
+    let mut buffer = ryu::Buffer::new();
+    for _ in 0..n {
+        let i = rand::random_range(0..1u32 << 24);
+        let expected = format!("{i}.0");
+        assert_eq!(buffer.format(i as f32), expected);
+        assert_eq!(buffer.format(-(i as f32)), format!("-{expected}"));
+    }
+}
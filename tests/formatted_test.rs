@@ -0,0 +1,59 @@
+#![allow(clippy::unreadable_literal)]
+
+use rand::RngExt as _;
+use std::collections::{BTreeSet, HashMap};
+use std::mem;
+
+#[test]
+fn test_format_owned() {
+    assert_eq!(ryu::format_owned(1.234f64), "1.234");
+    assert_eq!(ryu::format_owned(-1e-7f32), "-1e-7");
+    assert_eq!(ryu::format_owned(f64::NAN), "NaN");
+    assert_eq!(ryu::format_owned(f32::NEG_INFINITY), "-inf");
+    assert_eq!(
+        ryu::format_owned(-2.2250738585072014e-308f64),
+        "-2.2250738585072014e-308",
+    );
+    assert_eq!(mem::size_of::<ryu::FormattedFloat>(), 25);
+}
+
+#[test]
+fn test_traits() {
+    let a = ryu::format_owned(0.5f64);
+    let b = a;
+    assert_eq!(a, b);
+    assert_eq!(a, ryu::format_owned(0.5f32));
+    assert!(a.starts_with("0."));
+    assert_eq!(a.to_string(), "0.5");
+    assert_eq!(format!("{a:>5}|{a:?}"), "  0.5|\"0.5\"");
+    assert!(ryu::format_owned(10.0f64) < ryu::format_owned(9.0f64));
+
+    let mut map = HashMap::new();
+    map.insert(ryu::format_owned(1.5f64), 1);
+    map.insert(ryu::format_owned(1.5f32), 2);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map["1.5"], 2);
+
+    let set = [3.0, 1e-3, 20.0]
+        .into_iter()
+        .map(ryu::format_owned::<f64>)
+        .collect::<BTreeSet<_>>();
+    let sorted = set
+        .iter()
+        .map(ryu::FormattedFloat::as_str)
+        .collect::<Vec<_>>();
+    assert_eq!(sorted, ["0.001", "20.0", "3.0"]);
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 100 } else { 10000 };
+    let mut rng = rand::rng();
+    let mut buffer = ryu::Buffer::new();
+    for _ in 0..n {
+        let f = f64::from_bits(rng.random());
+        assert_eq!(ryu::format_owned(f), buffer.format(f));
+        let f = f32::from_bits(rng.random());
+        assert_eq!(ryu::format_owned(f), buffer.format(f));
+    }
+}
@@ -0,0 +1,208 @@
+#![allow(
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::float_cmp,
+    clippy::similar_names,
+    clippy::unreadable_literal
+)]
+
+use ryu::ParseError;
+
+// Exact value of a finite FP8 bit pattern, computed independently of the
+// crate from the format's field widths.
+fn value(bits: u8, mantissa_bits: u32, bias: i32) -> f64 {
+    let sign = if bits & 0x80 != 0 { -1.0 } else { 1.0 };
+    let magnitude = bits & 0x7f;
+    let ieee_mantissa = (magnitude & ((1 << mantissa_bits) - 1)) as f64;
+    let ieee_exponent = (magnitude >> mantissa_bits) as i32;
+    let scale = (1u32 << mantissa_bits) as f64;
+    if ieee_exponent == 0 {
+        sign * ieee_mantissa / scale * 2f64.powi(1 - bias)
+    } else {
+        sign * (1.0 + ieee_mantissa / scale) * 2f64.powi(ieee_exponent - bias)
+    }
+}
+
+fn e4m3(bits: u8) -> f64 {
+    value(bits, 3, 7)
+}
+
+fn e5m2(bits: u8) -> f64 {
+    if bits & 0x7f == 0x7c {
+        f64::INFINITY.copysign(e5m2(bits & 0x80))
+    } else {
+        value(bits, 2, 15)
+    }
+}
+
+// Exact decimal representation of an f64 that has few significant bits.
+fn exact(f: f64) -> String {
+    let s = format!("{f:.30}");
+    s.trim_end_matches('0').to_owned()
+}
+
+fn check_exhaustive(
+    format: fn(&mut ryu::Buffer, u8) -> &str,
+    parse: fn(&[u8]) -> Result<u8, ParseError>,
+    value: fn(u8) -> f64,
+    is_nan: fn(u8) -> bool,
+    nan: u8,
+    max_finite: u8,
+) {
+    let mut buffer = ryu::Buffer::new();
+    for bits in 0..=u8::MAX {
+        let printed = format(&mut buffer, bits).to_owned();
+        let parsed = parse(printed.as_bytes()).unwrap();
+        if is_nan(bits) {
+            assert_eq!(printed, "NaN");
+            assert_eq!(parsed, nan);
+            continue;
+        }
+        assert_eq!(parsed, bits, "{bits:#04x} printed as {printed}");
+        let f = value(bits);
+        if f.is_finite() {
+            assert_eq!(printed.parse::<f64>().unwrap().abs() == 0.0, f == 0.0);
+            assert_eq!(parse(exact(f).as_bytes()).unwrap(), bits);
+            assert_eq!(parse(format!("{f:e}").as_bytes()).unwrap(), bits);
+        }
+
+        // No decimal with fewer significant digits may round trip, other than
+        // through saturation above the largest finite value.
+        let digits = printed
+            .split('e')
+            .next()
+            .unwrap()
+            .bytes()
+            .skip_while(|&b| b == b'-' || b == b'0' || b == b'.')
+            .filter(u8::is_ascii_digit)
+            .collect::<Vec<u8>>();
+        let digits = digits.len() - digits.iter().rev().take_while(|&&b| b == b'0').count();
+        if f.is_finite() && f != 0.0 && digits > 1 {
+            let shorter = format!("{:.*e}", digits - 2, f);
+            let (mantissa, exponent) = shorter.split_once('e').unwrap();
+            let mantissa: f64 = mantissa.parse().unwrap();
+            let step = 10f64.powi(2 - digits as i32) * mantissa.signum();
+            for candidate in [mantissa - step, mantissa, mantissa + step] {
+                let candidate = format!("{candidate:.*}e{exponent}", digits - 2);
+                if candidate.parse::<f64>().unwrap().abs() > value(max_finite) {
+                    continue;
+                }
+                assert_ne!(parse(candidate.as_bytes()).unwrap(), bits, "{candidate}");
+            }
+        }
+    }
+}
+
+fn check_ties(parse: fn(&[u8]) -> Result<u8, ParseError>, value: fn(u8) -> f64, max_finite: u8) {
+    for bits in 0..max_finite {
+        let lo = value(bits);
+        let hi = value(bits + 1);
+        let midpoint = (lo + hi) / 2.0;
+        let even = if bits % 2 == 0 { bits } else { bits + 1 };
+        assert_eq!(parse(exact(midpoint).as_bytes()).unwrap(), even);
+        let below = f64::from_bits(midpoint.to_bits() - 1);
+        assert_eq!(parse(below.to_string().as_bytes()).unwrap(), bits);
+        let above = f64::from_bits(midpoint.to_bits() + 1);
+        assert_eq!(parse(above.to_string().as_bytes()).unwrap(), bits + 1);
+        let negative = format!("-{}", exact(midpoint));
+        assert_eq!(parse(negative.as_bytes()).unwrap(), 0x80 | even);
+    }
+}
+
+#[test]
+fn test_e4m3_exhaustive() {
+    check_exhaustive(
+        ryu::Buffer::format_e4m3,
+        ryu::parse_e4m3,
+        e4m3,
+        |bits| bits & 0x7f == 0x7f,
+        0x7f,
+        0x7e,
+    );
+    check_ties(ryu::parse_e4m3, e4m3, 0x7e);
+}
+
+#[test]
+fn test_e5m2_exhaustive() {
+    check_exhaustive(
+        ryu::Buffer::format_e5m2,
+        ryu::parse_e5m2,
+        e5m2,
+        |bits| bits & 0x7f > 0x7c,
+        0x7e,
+        0x7b,
+    );
+    check_ties(ryu::parse_e5m2, e5m2, 0x7b);
+}
+
+#[test]
+fn test_e4m3_basic() {
+    let mut buffer = ryu::Buffer::new();
+    assert_eq!(buffer.format_e4m3(0x00), "0.0");
+    assert_eq!(buffer.format_e4m3(0x80), "-0.0");
+    assert_eq!(buffer.format_e4m3(0x38), "1.0");
+    assert_eq!(buffer.format_e4m3(0x01), "0.002");
+    assert_eq!(buffer.format_e4m3(0x08), "0.016");
+    assert_eq!(buffer.format_e4m3(0x7d), "420.0");
+    assert_eq!(buffer.format_e4m3(0x7e), "450.0");
+    assert_eq!(buffer.format_e4m3(0xfe), "-450.0");
+    assert_eq!(buffer.format_e4m3(0x7f), "NaN");
+    assert_eq!(buffer.format_e4m3(0xff), "NaN");
+
+    assert_eq!(ryu::parse_e4m3(b"-0"), Ok(0x80));
+    assert_eq!(ryu::parse_e4m3(b"1e-10"), Ok(0x00));
+    assert_eq!(ryu::parse_e4m3(b"-1e-10"), Ok(0x80));
+    assert_eq!(ryu::parse_e4m3(b"0.000976"), Ok(0x00));
+    assert_eq!(ryu::parse_e4m3(b"0.000977"), Ok(0x01));
+}
+
+#[test]
+fn test_e4m3_saturation() {
+    assert_eq!(ryu::parse_e4m3(b"448"), Ok(0x7e));
+    assert_eq!(ryu::parse_e4m3(b"464"), Ok(0x7e));
+    assert_eq!(ryu::parse_e4m3(b"465"), Ok(0x7e));
+    assert_eq!(ryu::parse_e4m3(b"1e300"), Ok(0x7e));
+    assert_eq!(ryu::parse_e4m3(b"-1e300"), Ok(0xfe));
+    assert_eq!(ryu::parse_e4m3(b"inf"), Ok(0x7e));
+    assert_eq!(ryu::parse_e4m3(b"-inf"), Ok(0xfe));
+    assert_eq!(ryu::parse_e4m3(b"NaN"), Ok(0x7f));
+}
+
+#[test]
+fn test_e5m2_basic() {
+    let mut buffer = ryu::Buffer::new();
+    assert_eq!(buffer.format_e5m2(0x00), "0.0");
+    assert_eq!(buffer.format_e5m2(0x80), "-0.0");
+    assert_eq!(buffer.format_e5m2(0x3c), "1.0");
+    assert_eq!(buffer.format_e5m2(0x01), "0.00002");
+    assert_eq!(buffer.format_e5m2(0x7b), "60000.0");
+    assert_eq!(buffer.format_e5m2(0x7c), "inf");
+    assert_eq!(buffer.format_e5m2(0xfc), "-inf");
+    assert_eq!(buffer.format_e5m2(0x7d), "NaN");
+    assert_eq!(buffer.format_e5m2(0xff), "NaN");
+}
+
+#[test]
+fn test_e5m2_saturation() {
+    assert_eq!(ryu::parse_e5m2(b"57344"), Ok(0x7b));
+    assert_eq!(ryu::parse_e5m2(b"61440"), Ok(0x7b));
+    assert_eq!(ryu::parse_e5m2(b"1e300"), Ok(0x7b));
+    assert_eq!(ryu::parse_e5m2(b"-1e300"), Ok(0xfb));
+    assert_eq!(ryu::parse_e5m2(b"inf"), Ok(0x7c));
+    assert_eq!(ryu::parse_e5m2(b"-inf"), Ok(0xfc));
+    assert_eq!(ryu::parse_e5m2(b"NaN"), Ok(0x7e));
+}
+
+#[test]
+fn test_malformed() {
+    assert_eq!(ryu::parse_e4m3(b""), Err(ParseError::InputTooShort));
+    assert_eq!(ryu::parse_e4m3(b"1.2.3"), Err(ParseError::MalformedInput));
+    assert_eq!(ryu::parse_e5m2(b"1x"), Err(ParseError::MalformedInput));
+    assert_eq!(ryu::parse_e5m2(b"-nan"), Err(ParseError::MalformedInput));
+    assert_eq!(ryu::parse_e5m2(b"1.00000000000000000"), Ok(0x3c));
+    assert_eq!(
+        ryu::parse_e5m2(b"1.00000000000000001"),
+        Err(ParseError::InputTooLong),
+    );
+}
@@ -0,0 +1,136 @@
+#![allow(
+    clippy::cast_lossless,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::unreadable_literal
+)]
+
+use rand::RngExt as _;
+
+// Decimal digits of m * 2^k.
+fn pow2_times(m: u64, k: u32) -> String {
+    let mut digits = m
+        .to_string()
+        .bytes()
+        .rev()
+        .map(|b| b - b'0')
+        .collect::<Vec<u8>>();
+    for _ in 0..k {
+        let mut carry = 0;
+        for digit in &mut digits {
+            let v = *digit * 2 + carry;
+            *digit = v % 10;
+            carry = v / 10;
+        }
+        if carry != 0 {
+            digits.push(carry);
+        }
+    }
+    digits.iter().rev().map(|d| (b'0' + d) as char).collect()
+}
+
+fn expected(negative: bool, m: u64, e: i32) -> String {
+    let sign = if negative { "-" } else { "" };
+    if m == 0 {
+        format!("{sign}0/1")
+    } else if e >= 0 {
+        format!("{sign}{}/1", pow2_times(m, e as u32))
+    } else {
+        let shift = m.trailing_zeros().min(-e as u32);
+        format!("{sign}{}/{}", m >> shift, pow2_times(1, -e as u32 - shift))
+    }
+}
+
+fn check64(f: f64) {
+    let bits = f.to_bits();
+    let ieee_mantissa = bits & ((1 << 52) - 1);
+    let ieee_exponent = (bits >> 52) as i32 & 0x7ff;
+    let (m, e) = if ieee_exponent == 0 {
+        (ieee_mantissa, -1074)
+    } else {
+        (ieee_mantissa | 1 << 52, ieee_exponent - 1075)
+    };
+    let mut buffer = ryu::FractionBuffer::new();
+    assert_eq!(buffer.format(f), expected(f.is_sign_negative(), m, e));
+}
+
+fn check32(f: f32) {
+    let bits = f.to_bits();
+    let ieee_mantissa = bits & ((1 << 23) - 1);
+    let ieee_exponent = (bits >> 23) as i32 & 0xff;
+    let (m, e) = if ieee_exponent == 0 {
+        (ieee_mantissa, -149)
+    } else {
+        (ieee_mantissa | 1 << 23, ieee_exponent - 150)
+    };
+    let mut buffer = ryu::FractionBuffer::new();
+    assert_eq!(
+        buffer.format(f),
+        expected(f.is_sign_negative(), m as u64, e)
+    );
+}
+
+#[test]
+fn test_fraction() {
+    let mut buffer = ryu::FractionBuffer::new();
+    assert_eq!(buffer.format(0.1f64), "3602879701896397/36028797018963968");
+    assert_eq!(buffer.format(0.1f32), "13421773/134217728");
+    assert_eq!(buffer.format(0.5f64), "1/2");
+    assert_eq!(buffer.format(-3.0f64), "-3/1");
+    assert_eq!(buffer.format(0.0f64), "0/1");
+    assert_eq!(buffer.format(-0.0f32), "-0/1");
+    assert_eq!(buffer.format(1e23f64), "99999999999999991611392/1");
+    assert_eq!(
+        buffer.format(5e-324f64),
+        format!("1/{}", pow2_times(1, 1074)),
+    );
+    assert_eq!(buffer.format(f64::NAN), "NaN");
+    assert_eq!(buffer.format(f32::NEG_INFINITY), "-inf");
+
+    for f in [5e-324, 2.2250738585072014e-308, 1.0, 1e100, f64::MAX] {
+        check64(f);
+        check64(-f);
+    }
+    for f in [1e-45, 1.1754944e-38, 1.0, 1e30, f32::MAX] {
+        check32(f);
+        check32(-f);
+    }
+}
+
+#[test]
+fn test_max_len() {
+    let mut buffer = ryu::FractionBuffer::new();
+    let mut max_len = 0;
+    for ieee_exponent in 0..0x7ff {
+        for ieee_mantissa in [1, (1 << 52) - 1] {
+            let f = -f64::from_bits(ieee_exponent << 52 | ieee_mantissa);
+            max_len = max_len.max(buffer.format(f).len());
+        }
+    }
+    assert_eq!(max_len, 342);
+
+    let mut max_len = 0;
+    for ieee_exponent in 0..0xff {
+        for ieee_mantissa in [1, (1 << 23) - 1] {
+            let f = -f32::from_bits(ieee_exponent << 23 | ieee_mantissa);
+            max_len = max_len.max(buffer.format(f).len());
+        }
+    }
+    assert_eq!(max_len, 55);
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 10 } else { 1000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let f = f64::from_bits(rng.random());
+        if f.is_finite() {
+            check64(f);
+        }
+        let f = f32::from_bits(rng.random());
+        if f.is_finite() {
+            check32(f);
+        }
+    }
+}
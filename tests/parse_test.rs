@@ -0,0 +1,327 @@
+#![allow(
+    clippy::cast_possible_wrap,
+    clippy::float_cmp,
+    clippy::unreadable_literal
+)]
+
+use rand::RngExt as _;
+use ryu::{Exactness, ParseError, Rounding};
+use std::cmp::Ordering;
+
+#[test]
+fn test_special() {
+    assert!(ryu::parse::<f64>(b"NaN").unwrap().is_nan());
+    assert!(ryu::parse::<f32>(b"NaN").unwrap().is_nan());
+    assert_eq!(ryu::parse::<f64>(b"inf"), Ok(f64::INFINITY));
+    assert_eq!(ryu::parse::<f32>(b"-inf"), Ok(f32::NEG_INFINITY));
+    assert_eq!(ryu::parse::<f64>(b"nan"), Err(ParseError::MalformedInput));
+    assert_eq!(ryu::parse::<f64>(b"+inf"), Err(ParseError::MalformedInput));
+    assert_eq!(ryu::parse::<f64>(b""), Err(ParseError::InputTooShort));
+}
+
+#[test]
+fn test_round_trip() {
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut buffer = ryu::Buffer::new();
+    for _ in 0..n {
+        let f: f64 = rand::random();
+        assert_eq!(ryu::parse(buffer.format(f).as_bytes()), Ok(f));
+        let f = f64::from_bits(rand::random());
+        if !f.is_nan() {
+            assert_eq!(ryu::parse(buffer.format(f).as_bytes()), Ok(f));
+        }
+        let f = f32::from_bits(rand::random());
+        if !f.is_nan() {
+            assert_eq!(ryu::parse(buffer.format(f).as_bytes()), Ok(f));
+        }
+    }
+}
+
+#[test]
+fn test_empty_digits() {
+    // A mantissa needs at least one digit, and so does an exponent once there
+    // is an `e`.
+    for string in [
+        &b"-"[..],
+        b".",
+        b"-.",
+        b".e1",
+        b"e1",
+        b"-e1",
+        b"1e",
+        b"1e-",
+        b"1.e+",
+    ] {
+        let string_lossy = String::from_utf8_lossy(string);
+        assert_eq!(
+            ryu::parse::<f64>(string),
+            Err(ParseError::MalformedInput),
+            "{string_lossy}"
+        );
+        assert_eq!(
+            ryu::parse::<f32>(string),
+            Err(ParseError::MalformedInput),
+            "{string_lossy}"
+        );
+    }
+    assert_eq!(ryu::parse::<f64>(b"1."), Ok(1.0));
+    assert_eq!(ryu::parse::<f64>(b".5"), Ok(0.5));
+    assert_eq!(ryu::parse::<f32>(b"-.5e1"), Ok(-5.0));
+}
+
+#[test]
+fn test_trailing_zeros() {
+    // Zeros past the digit limit only scale the mantissa, so they do not count
+    // toward it.
+    assert_eq!(
+        ryu::parse::<f64>(b"123456789012345670"),
+        Ok(123456789012345670.0)
+    );
+    assert_eq!(
+        ryu::parse::<f64>(b"12345678901234567.0000000000"),
+        Ok(12345678901234567.0)
+    );
+    assert_eq!(
+        ryu::parse::<f64>(b"123456789012345670.1"),
+        Err(ParseError::InputTooLong)
+    );
+    assert_eq!(ryu::parse::<f32>(b"1234567890"), Ok(1234567890.0));
+    assert_eq!(ryu::parse::<f32>(b"1.000000000000e3"), Ok(1000.0));
+    assert_eq!(
+        ryu::parse::<f32>(b"1234567891"),
+        Err(ParseError::InputTooLong)
+    );
+
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let m10 = rng.random_range(1..100_000_000_000_000_000u64);
+        let mut string = m10.to_string() + &"0".repeat(rng.random_range(0..30));
+        string.insert(rng.random_range(1..=string.len()), '.');
+        let e10 = rng.random_range(-300..280);
+        let string = format!("{string}e{e10}");
+        let expected = string.parse::<f64>().unwrap();
+        assert_eq!(ryu::parse(string.as_bytes()), Ok(expected), "{string}");
+
+        let m10 = m10 % 1_000_000_000;
+        let string = m10.to_string() + &"0".repeat(rng.random_range(0..30));
+        let e10 = rng.random_range(-60..10);
+        let string = format!("{string}e{e10}");
+        let expected = string.parse::<f32>().unwrap();
+        assert_eq!(ryu::parse(string.as_bytes()), Ok(expected), "{string}");
+    }
+}
+
+// Significant digits, without trailing zeros, and the exponent of the first
+// one, for a positive decimal in scientific notation.
+fn normalize(scientific: &str) -> (String, i32) {
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    (
+        digits.trim_end_matches('0').to_owned(),
+        exponent.parse().unwrap(),
+    )
+}
+
+fn expected_exactness(decimal: (String, i32), exact: &str, is_normal: bool) -> Exactness {
+    let (digits, exponent) = decimal;
+    let (exact_digits, exact_exponent) = normalize(exact);
+    match (exact_exponent, exact_digits).cmp(&(exponent, digits)) {
+        Ordering::Equal => Exactness::Exact,
+        _ if !is_normal => Exactness::Underflow,
+        Ordering::Greater => Exactness::RoundedUp,
+        Ordering::Less => Exactness::RoundedDown,
+    }
+}
+
+#[test]
+fn test_exactness() {
+    assert_eq!(
+        ryu::parse_with_exactness::<f64>(b"1e400"),
+        Ok((f64::INFINITY, Exactness::Overflow)),
+    );
+    assert_eq!(
+        ryu::parse_with_exactness::<f64>(b"-1e-400"),
+        Ok((-0.0, Exactness::Underflow)),
+    );
+    assert_eq!(
+        ryu::parse_with_exactness::<f64>(b"1.7976931348623159e308"),
+        Ok((f64::INFINITY, Exactness::Overflow)),
+    );
+    assert_eq!(
+        ryu::parse_with_exactness::<f64>(b"4.9406564584124654e-324"),
+        Ok((5e-324, Exactness::Underflow)),
+    );
+    assert_eq!(
+        ryu::parse_with_exactness::<f32>(b"1e-45"),
+        Ok((1e-45, Exactness::Underflow)),
+    );
+    assert_eq!(
+        ryu::parse_with_exactness::<f32>(b"-0.0"),
+        Ok((-0.0, Exactness::Exact)),
+    );
+    assert_eq!(
+        ryu::parse_with_exactness::<f32>(b"16777217"),
+        Ok((16777216.0, Exactness::RoundedDown)),
+    );
+    assert_eq!(
+        ryu::parse_with_exactness::<f32>(b"-16777219"),
+        Ok((-16777220.0, Exactness::RoundedDown)),
+    );
+
+    // Compare every result against the exact decimal expansion of the float.
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let m10 =
+            (rng.random_range(1..100_000_000_000_000_000u64) >> rng.random_range(0..57)).max(1);
+        let e10 = rng.random_range(-340..310);
+        let decimal = (
+            m10.to_string().trim_end_matches('0').to_owned(),
+            e10 + m10.ilog10() as i32,
+        );
+        let string = format!("{m10}e{e10}");
+        let (f, exactness) = ryu::parse_with_exactness::<f64>(string.as_bytes()).unwrap();
+        assert_eq!(Ok(f), ryu::parse::<f64>(string.as_bytes()));
+        let expected = if f.is_infinite() {
+            Exactness::Overflow
+        } else if f == 0.0 {
+            Exactness::Underflow
+        } else {
+            expected_exactness(decimal.clone(), &format!("{f:.800e}"), f.is_normal())
+        };
+        assert_eq!(exactness, expected, "{string}");
+
+        let m10 = m10 % 1_000_000_000;
+        if m10 == 0 {
+            continue;
+        }
+        let e10 = e10 % 50;
+        let decimal = (
+            m10.to_string().trim_end_matches('0').to_owned(),
+            e10 + m10.ilog10() as i32,
+        );
+        let string = format!("{m10}e{e10}");
+        let (f, exactness) = ryu::parse_with_exactness::<f32>(string.as_bytes()).unwrap();
+        assert_eq!(Ok(f), ryu::parse::<f32>(string.as_bytes()));
+        let expected = if f.is_infinite() {
+            Exactness::Overflow
+        } else if f == 0.0 {
+            Exactness::Underflow
+        } else {
+            expected_exactness(decimal, &format!("{f:.200e}"), f.is_normal())
+        };
+        assert_eq!(exactness, expected, "{string}");
+    }
+}
+
+macro_rules! check_rounding {
+    ($float:ty, $string:expr) => {{
+        let string: &str = $string;
+        let parse = |rounding| ryu::parse_rounded::<$float>(string.as_bytes(), rounding).unwrap();
+        let (nearest, exactness) = ryu::parse_with_exactness::<$float>(string.as_bytes()).unwrap();
+        let negative = nearest.is_sign_negative();
+        let (toward_zero, away_from_zero) = match exactness {
+            Exactness::Exact => (nearest, nearest),
+            Exactness::RoundedUp | Exactness::RoundedDown => {
+                let next_toward_zero = <$float>::from_bits(nearest.to_bits() - 1);
+                let next_away_from_zero = <$float>::from_bits(nearest.to_bits() + 1);
+                if (exactness == Exactness::RoundedUp) != negative {
+                    (next_toward_zero, nearest)
+                } else {
+                    (nearest, next_away_from_zero)
+                }
+            }
+            Exactness::Overflow | Exactness::Underflow => unreachable!(),
+        };
+        let (downward, upward) = if negative {
+            (away_from_zero, toward_zero)
+        } else {
+            (toward_zero, away_from_zero)
+        };
+        assert_eq!(parse(Rounding::NearestEven), nearest, "{}", string);
+        assert_eq!(parse(Rounding::TowardZero), toward_zero, "{}", string);
+        assert_eq!(parse(Rounding::Upward), upward, "{}", string);
+        assert_eq!(parse(Rounding::Downward), downward, "{}", string);
+    }};
+}
+
+#[test]
+fn test_rounding() {
+    let parse64 = |string: &[u8], rounding| ryu::parse_rounded::<f64>(string, rounding).unwrap();
+    assert_eq!(parse64(b"1e-400", Rounding::Upward), 5e-324);
+    assert_eq!(parse64(b"1e-400", Rounding::Downward), 0.0);
+    assert_eq!(parse64(b"-1e-400", Rounding::Downward), -5e-324);
+    assert_eq!(
+        parse64(b"-1e-400", Rounding::Upward).to_bits(),
+        (-0.0f64).to_bits()
+    );
+    assert_eq!(parse64(b"3e-324", Rounding::Downward), 0.0);
+    assert_eq!(parse64(b"3e-324", Rounding::Upward), 5e-324);
+    assert_eq!(parse64(b"1e400", Rounding::TowardZero), f64::MAX);
+    assert_eq!(parse64(b"1e400", Rounding::Upward), f64::INFINITY);
+    assert_eq!(parse64(b"-1e400", Rounding::Upward), f64::MIN);
+    assert_eq!(parse64(b"-1e400", Rounding::Downward), f64::NEG_INFINITY);
+    assert_eq!(
+        parse64(b"1.7976931348623158e308", Rounding::NearestEven),
+        f64::MAX
+    );
+    assert_eq!(
+        parse64(b"1.7976931348623158e308", Rounding::Upward),
+        f64::INFINITY
+    );
+    assert_eq!(
+        parse64(b"1.7976931348623158e308", Rounding::Downward),
+        f64::MAX
+    );
+    assert_eq!(parse64(b"inf", Rounding::TowardZero), f64::INFINITY);
+
+    let parse32 = |string: &[u8], rounding| ryu::parse_rounded::<f32>(string, rounding).unwrap();
+    assert_eq!(parse32(b"1e-50", Rounding::Upward), 1e-45);
+    assert_eq!(
+        parse32(b"-1e-50", Rounding::TowardZero).to_bits(),
+        (-0.0f32).to_bits()
+    );
+    assert_eq!(parse32(b"1e50", Rounding::Downward), f32::MAX);
+    assert_eq!(parse32(b"3.4028236e38", Rounding::TowardZero), f32::MAX);
+    assert_eq!(parse32(b"3.4028236e38", Rounding::Upward), f32::INFINITY);
+
+    // Each directed result must be the nearest float, or its neighbor on the
+    // other side of the decimal.
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let m10 = rng.random_range(1..100_000_000_000_000_000u64) >> rng.random_range(0..57);
+        let sign = if rng.random() { "-" } else { "" };
+        let e10 = rng.random_range(-300..290);
+        check_rounding!(f64, &format!("{sign}{m10}e{e10}"));
+        let e10 = rng.random_range(-30..30);
+        check_rounding!(f32, &format!("{}{}e{}", sign, m10 % 1_000_000_000, e10));
+    }
+}
+
+#[test]
+fn test_digit_limit() {
+    let digits18 = b"100000000000000001";
+    assert_eq!(ryu::parse::<f64>(digits18), Err(ParseError::InputTooLong));
+    assert_eq!(
+        ryu::parse_with_exactness::<f64>(digits18),
+        Err(ParseError::InputTooLong)
+    );
+    assert_eq!(
+        ryu::parse_rounded::<f64>(digits18, Rounding::NearestEven),
+        Err(ParseError::InputTooLong)
+    );
+    assert_eq!(
+        ryu::parse_rounded::<f64>(digits18, Rounding::Downward),
+        Ok(1e17)
+    );
+    assert_eq!(
+        ryu::parse_rounded::<f64>(digits18, Rounding::Upward),
+        Ok(100000000000000016.0)
+    );
+    assert_eq!(
+        ryu::parse_rounded::<f64>(b"1000000000000000001", Rounding::Downward),
+        Err(ParseError::InputTooLong)
+    );
+}
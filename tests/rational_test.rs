@@ -0,0 +1,90 @@
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::float_cmp,
+    clippy::unreadable_literal
+)]
+
+use rand::RngExt as _;
+
+#[test]
+fn test_simplest_fraction() {
+    assert_eq!(ryu::simplest_fraction(0.0f64), Some((0, 1)));
+    assert_eq!(ryu::simplest_fraction(-0.0f32), Some((0, 1)));
+    assert_eq!(ryu::simplest_fraction(1.0f64), Some((1, 1)));
+    assert_eq!(ryu::simplest_fraction(-7.0f32), Some((-7, 1)));
+    assert_eq!(ryu::simplest_fraction(0.5f64), Some((1, 2)));
+    assert_eq!(ryu::simplest_fraction(0.1f64), Some((1, 10)));
+    assert_eq!(ryu::simplest_fraction(0.1f32), Some((1, 10)));
+    assert_eq!(ryu::simplest_fraction(1.0f64 / 3.0), Some((1, 3)));
+    assert_eq!(ryu::simplest_fraction(-2.0f64 / 7.0), Some((-2, 7)));
+    assert_eq!(ryu::simplest_fraction(22.0f32 / 7.0), Some((22, 7)));
+    assert_eq!(ryu::simplest_fraction(0.3f32), Some((3, 10)));
+    assert_eq!(ryu::simplest_fraction(1e-10f64), Some((1, 10000000000)));
+    assert_eq!(
+        ryu::simplest_fraction(9007199254740993.0f64),
+        Some((9007199254740992, 1)),
+    );
+    assert_eq!(
+        ryu::simplest_fraction(4503599627370495.5f64),
+        Some((9007199254740991, 2)),
+    );
+    assert_eq!(
+        ryu::simplest_fraction(9.223372036854775e18f64),
+        Some((9223372036854774784, 1)),
+    );
+    assert_eq!(ryu::simplest_fraction(9.223372036854776e18f64), None);
+    assert_eq!(ryu::simplest_fraction(1e-20f64), None);
+    assert_eq!(ryu::simplest_fraction(5e-324f64), None);
+    assert_eq!(ryu::simplest_fraction(f64::NAN), None);
+    assert_eq!(ryu::simplest_fraction(f32::NEG_INFINITY), None);
+}
+
+// Whether p/q rounds to f, for p and q below 2^53.
+fn rounds_to(p: i64, q: u64, f: f64) -> bool {
+    p as f64 / q as f64 == f
+}
+
+#[test]
+fn test_smallest_denominator() {
+    let n = if cfg!(miri) { 10 } else { 2000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let q = rng.random_range(1..2000u64);
+        let p = rng.random_range(-100000..100000i64);
+        let f = p as f64 / q as f64;
+        let (numerator, denominator) = ryu::simplest_fraction(f).unwrap();
+        assert!(rounds_to(numerator, denominator, f), "{p}/{q}");
+        assert!(denominator <= q, "{p}/{q}");
+        for smaller in 1..denominator {
+            let guess = (f * smaller as f64).round() as i64;
+            for p in guess - 1..=guess + 1 {
+                assert!(!rounds_to(p, smaller, f), "{p}/{smaller} {f}");
+            }
+        }
+
+        let f = p as f32 / q as f32;
+        let (numerator, denominator) = ryu::simplest_fraction(f).unwrap();
+        assert!(denominator <= q, "{p}/{q}");
+        assert_eq!(
+            ryu::simplest_fraction(numerator as f32 / denominator as f32),
+            Some((numerator, denominator)),
+        );
+    }
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let f = f64::from_bits(rng.random());
+        if let Some((numerator, denominator)) = ryu::simplest_fraction(f) {
+            if numerator.unsigned_abs() < 1 << 53 && denominator < 1 << 53 {
+                assert!(rounds_to(numerator, denominator, f), "{f:e}");
+            }
+        } else {
+            assert!(!f.is_finite() || f.abs() >= 9.223372036854776e18 || f.abs() < 1e-3);
+        }
+    }
+}
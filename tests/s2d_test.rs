@@ -18,7 +18,6 @@
 // is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
 // KIND, either express or implied.
 
-#![cfg(not(feature = "small"))]
 #![allow(dead_code)]
 #![allow(
     clippy::cast_lossless,
@@ -38,14 +37,14 @@
 #[path = "../src/common.rs"]
 mod common;
 
-#[cfg(not(feature = "small"))]
+#[cfg(not(any(feature = "small", feature = "tiny")))]
 #[path = "../src/d2s_full_table.rs"]
 mod d2s_full_table;
 
 #[path = "../src/d2s_intrinsics.rs"]
 mod d2s_intrinsics;
 
-#[cfg(feature = "small")]
+#[cfg(all(feature = "small", not(feature = "tiny")))]
 #[path = "../src/d2s_small_table.rs"]
 mod d2s_small_table;
 
@@ -60,12 +59,8 @@ mod parse;
 
 use crate::parse::Error;
 use crate::s2d::s2d;
-
-impl PartialEq for Error {
-    fn eq(&self, other: &Self) -> bool {
-        *self as u8 == *other as u8
-    }
-}
+use rand::RngExt as _;
+use std::fmt::Write as _;
 
 #[test]
 fn test_bad_input() {
@@ -75,6 +70,12 @@ fn test_bad_input() {
     assert_eq!(Error::MalformedInput, s2d(b"1..1").unwrap_err());
     assert_eq!(Error::MalformedInput, s2d(b"1ee1").unwrap_err());
     assert_eq!(Error::MalformedInput, s2d(b"1e.1").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2d(b"-").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2d(b".").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2d(b"-.e1").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2d(b"e1").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2d(b"1e").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2d(b"1e-").unwrap_err());
     assert_eq!(Error::InputTooShort, s2d(b"").unwrap_err());
     assert_eq!(Error::InputTooLong, s2d(b"123456789012345678").unwrap_err());
     assert_eq!(Error::InputTooLong, s2d(b"1e12345").unwrap_err());
@@ -97,6 +98,8 @@ fn test_basic() {
     assert_eq!(1.0, s2d(b"1E0").unwrap());
     assert_eq!(1.0, s2d(b"000001.000000").unwrap());
     assert_eq!(0.2316419, s2d(b"0.2316419").unwrap());
+    assert_eq!(1e30, s2d(b"1000000000000000000000000000000").unwrap());
+    assert_eq!(1.5, s2d(b"1.50000000000000000000").unwrap());
 }
 
 #[test]
@@ -165,3 +168,144 @@ fn test_issue173() {
         s2d(b"2.2250738585072014e-308").unwrap(),
     );
 }
+
+#[test]
+fn test_halfway() {
+    // Exactly halfway between two doubles, rounding to the even one.
+    assert_eq!(9007199254740992.0, s2d(b"9007199254740993").unwrap());
+    assert_eq!(9007199254740996.0, s2d(b"9007199254740995").unwrap());
+    assert_eq!(9007199254740992.0, s2d(b"9007199254740993.0").unwrap());
+    assert_eq!(9007199254740996.0, s2d(b"90071992547409950e-1").unwrap());
+    assert_eq!(1.8014398509481984e16, s2d(b"18014398509481986").unwrap());
+    assert_eq!(1.8014398509481992e16, s2d(b"18014398509481990").unwrap());
+    // Just above halfway.
+    assert_eq!(9007199254740994.0, s2d(b"9007199254740993.1").unwrap());
+    // Halfway between 2^53 and 2^53 + 2, times a power of 2.
+    assert_eq!(
+        1.8014398509481984e16,
+        s2d(b"1.8014398509481986e16").unwrap()
+    );
+}
+
+#[test]
+fn test_random_against_std() {
+    let n = if cfg!(miri) { 100 } else { 1000000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let digits = rng.random_range(1..=17);
+        let m10 = rng.random_range(0..10u64.pow(digits));
+        let e10 = rng.random_range(-345..=310);
+        let string = format!("{m10}e{e10}");
+        assert_eq!(
+            s2d(string.as_bytes()).unwrap(),
+            string.parse::<f64>().unwrap(),
+            "{string}",
+        );
+    }
+}
+
+#[test]
+fn test_integers_against_std() {
+    // Integers from 2^53 to 2^56 with up to 3 bits below the precision of a
+    // double, including every kind of exact tie.
+    let n = if cfg!(miri) { 100 } else { 300000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let m10 = rng.random_range(1u64 << 53..1u64 << 56);
+        for string in [
+            format!("{m10}"),
+            format!("{m10}0e-1"),
+            format!("{}e1", m10 / 10),
+            format!("{}.{}", m10 / 10, m10 % 10),
+        ] {
+            let expected = string.parse::<f64>().unwrap();
+            assert_eq!(s2d(string.as_bytes()).unwrap(), expected, "{string}");
+        }
+    }
+}
+
+#[test]
+fn test_parse_eight_digits() {
+    use crate::parse::parse_eight_digits;
+
+    assert_eq!(Some(12345678), parse_eight_digits(b"12345678", 0));
+    assert_eq!(Some(0), parse_eight_digits(b"00000000", 0));
+    assert_eq!(Some(99999999), parse_eight_digits(b"-99999999", 1));
+    assert_eq!(None, parse_eight_digits(b"1234567", 0));
+    assert_eq!(None, parse_eight_digits(b"-1234567", 1));
+    assert_eq!(None, parse_eight_digits(b"1234.678", 0));
+
+    // Every byte value in every position, against a byte at a time.
+    let mut rng = rand::rng();
+    for _ in 0..if cfg!(miri) { 100 } else { 100000 } {
+        let mut bytes = *b"12345678";
+        let pos = rng.random_range(0..8);
+        bytes[pos] = rng.random();
+        let expected = bytes.iter().try_fold(0, |m10, &c| {
+            c.is_ascii_digit().then(|| 10 * m10 + (c - b'0') as u32)
+        });
+        assert_eq!(parse_eight_digits(&bytes, 0), expected, "{bytes:?}");
+    }
+}
+
+#[test]
+fn test_long_digits_against_std() {
+    // Up to 17 significant digits after any number of leading zeros, with
+    // the dot anywhere, so that 8-digit chunks start at every offset.
+    let n = if cfg!(miri) { 100 } else { 300000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let zeros = rng.random_range(0..12);
+        let digits = rng.random_range(1..=17);
+        let m10 = rng.random_range(0..10u64.pow(digits));
+        let mut string = "0".repeat(zeros) + &format!("{:01$}", m10, digits as usize);
+        let dot = rng.random_range(0..=string.len());
+        string.insert(dot, '.');
+        if rng.random() {
+            write!(string, "e{}", rng.random_range(-30..30)).unwrap();
+        }
+        assert_eq!(
+            s2d(string.as_bytes()).unwrap(),
+            string.parse::<f64>().unwrap(),
+            "{string}",
+        );
+    }
+    assert_eq!(
+        Error::InputTooLong,
+        s2d(b"0000000000000000123456789.012345678").unwrap_err(),
+    );
+    assert_eq!(
+        1.2345678901234568e16,
+        s2d(b"12345678901234567.000000000").unwrap()
+    );
+}
+
+#[test]
+fn test_from_decimal() {
+    use crate::s2d::from_decimal;
+
+    assert_eq!(1.5, from_decimal(15, -1, false));
+    assert_eq!(-0.0, from_decimal(0, i32::MAX, true));
+    assert!(from_decimal(0, 0, true).is_sign_negative());
+    assert_eq!(f64::INFINITY, from_decimal(1, i32::MAX, false));
+    assert_eq!(-0.0, from_decimal(u64::MAX, i32::MIN, true));
+    assert_eq!(1.8446744073709552e19, from_decimal(u64::MAX, 0, false));
+    assert_eq!(0.0, from_decimal(18446744073709551615, -343, false));
+    assert_eq!(1.5e-323, from_decimal(12351641146031163605, -342, false));
+    assert_eq!(0.0, from_decimal(2470328229206232720, -342, false));
+    assert_eq!(5e-324, from_decimal(2470328229206232721, -342, false));
+    assert_eq!(f64::MAX, from_decimal(17976931348623157081, 289, false));
+
+    // Mantissas of every length up to 20 digits, over the whole exponent range.
+    let n = if cfg!(miri) { 100 } else { 300000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let m10 = rng.random::<u64>() >> rng.random_range(0..64);
+        let e10 = rng.random_range(-350..320);
+        assert_eq!(
+            from_decimal(m10, e10, false),
+            format!("{m10}e{e10}").parse::<f64>().unwrap(),
+            "{m10}e{e10}",
+        );
+    }
+}
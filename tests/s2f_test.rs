@@ -37,14 +37,14 @@
 #[path = "../src/common.rs"]
 mod common;
 
-#[cfg(not(feature = "small"))]
+#[cfg(not(any(feature = "small", feature = "tiny")))]
 #[path = "../src/d2s_full_table.rs"]
 mod d2s_full_table;
 
 #[path = "../src/d2s_intrinsics.rs"]
 mod d2s_intrinsics;
 
-#[cfg(feature = "small")]
+#[cfg(all(feature = "small", not(feature = "tiny")))]
 #[path = "../src/d2s_small_table.rs"]
 mod d2s_small_table;
 
@@ -57,6 +57,9 @@ mod f2s_intrinsics;
 #[path = "../src/f2s.rs"]
 mod f2s;
 
+#[path = "../src/s2d.rs"]
+mod s2d;
+
 #[path = "../src/s2f.rs"]
 mod s2f;
 
@@ -65,11 +68,19 @@ mod parse;
 
 use crate::parse::Error;
 use crate::s2f::s2f;
+use rand::RngExt as _;
+use std::fmt::Write as _;
 
-impl PartialEq for Error {
-    fn eq(&self, other: &Self) -> bool {
-        *self as u8 == *other as u8
-    }
+#[test]
+fn test_bad_input() {
+    assert_eq!(Error::MalformedInput, s2f(b"x").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2f(b"1..1").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2f(b"-").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2f(b".").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2f(b"e1").unwrap_err());
+    assert_eq!(Error::MalformedInput, s2f(b"1e+").unwrap_err());
+    assert_eq!(Error::InputTooShort, s2f(b"").unwrap_err());
+    assert_eq!(Error::InputTooLong, s2f(b"1234567891").unwrap_err());
 }
 
 #[test]
@@ -80,6 +91,8 @@ fn test_basic() {
     assert_eq!(-1.0, s2f(b"-1").unwrap());
     assert_eq!(123456792.0, s2f(b"123456789").unwrap());
     assert_eq!(299792448.0, s2f(b"299792458").unwrap());
+    assert_eq!(79752880000.0, s2f(b"79752880000.0").unwrap());
+    assert_eq!(1e-6, s2f(b"0.000001000000000").unwrap());
 }
 
 #[test]
@@ -107,3 +120,57 @@ fn test_trailing_zeros() {
     assert_eq!(50000004.0, s2f(b"50000002.5").unwrap());
     assert_eq!(99999992.0, s2f(b"99999989.5").unwrap());
 }
+
+#[test]
+fn test_long_digits_against_std() {
+    // Up to 9 significant digits after any number of leading zeros, with the
+    // dot anywhere, so that 8-digit chunks start at every offset.
+    let n = if cfg!(miri) { 100 } else { 300000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let zeros = rng.random_range(0..12);
+        let digits = rng.random_range(1..=9);
+        let m10 = rng.random_range(0..10u32.pow(digits));
+        let mut string = "0".repeat(zeros) + &format!("{:01$}", m10, digits as usize);
+        let dot = rng.random_range(0..=string.len());
+        string.insert(dot, '.');
+        if rng.random() {
+            write!(string, "e{}", rng.random_range(-30..30)).unwrap();
+        }
+        assert_eq!(
+            s2f(string.as_bytes()).unwrap(),
+            string.parse::<f32>().unwrap(),
+            "{string}",
+        );
+    }
+    assert_eq!(Error::InputTooLong, s2f(b"000000001234567891").unwrap_err(),);
+    assert_eq!(123456790.0, s2f(b"123456789.000000000").unwrap());
+}
+
+#[test]
+fn test_from_decimal() {
+    use crate::s2f::from_decimal;
+
+    assert_eq!(1.5, from_decimal(15, -1, false));
+    assert_eq!(-0.0, from_decimal(0, i32::MAX, true));
+    assert!(from_decimal(0, 0, true).is_sign_negative());
+    assert_eq!(f32::INFINITY, from_decimal(1, i32::MAX, false));
+    assert_eq!(-0.0, from_decimal(u64::MAX, i32::MIN, true));
+    assert_eq!(1.0e-45, from_decimal(14012984643248170709, -64, false));
+    assert_eq!(0.0, from_decimal(7006492321624085354, -64, false));
+    assert_eq!(f32::MAX, from_decimal(3402823567797336616, 20, false));
+
+    // Mantissas of every length up to 20 digits, through both the 32-bit and
+    // the 64-bit conversion.
+    let n = if cfg!(miri) { 100 } else { 300000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let m10 = rng.random::<u64>() >> rng.random_range(0..64);
+        let e10 = rng.random_range(-70..50);
+        assert_eq!(
+            from_decimal(m10, e10, false),
+            format!("{m10}e{e10}").parse::<f32>().unwrap(),
+            "{m10}e{e10}",
+        );
+    }
+}
@@ -0,0 +1,279 @@
+#![allow(dead_code)]
+#![allow(
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::checked_conversions,
+    clippy::manual_range_contains,
+    clippy::many_single_char_names,
+    clippy::similar_names,
+    clippy::too_many_lines,
+    clippy::unreadable_literal,
+    clippy::unseparated_literal_suffix,
+    clippy::wildcard_imports
+)]
+
+#[path = "../src/common.rs"]
+mod common;
+
+#[cfg(not(any(feature = "small", feature = "tiny")))]
+#[path = "../src/d2s_full_table.rs"]
+mod d2s_full_table;
+
+#[path = "../src/d2s_intrinsics.rs"]
+mod d2s_intrinsics;
+
+#[cfg(all(feature = "small", not(feature = "tiny")))]
+#[path = "../src/d2s_small_table.rs"]
+mod d2s_small_table;
+
+#[path = "../src/d2s.rs"]
+mod d2s;
+
+#[path = "../src/f2s_intrinsics.rs"]
+mod f2s_intrinsics;
+
+#[path = "../src/f2s.rs"]
+mod f2s;
+
+#[path = "../src/schubfach.rs"]
+mod schubfach;
+
+use rand::RngExt as _;
+use std::cmp::Ordering;
+
+// Both backends must agree on every input, not just round trip.
+fn check64(bits: u64) {
+    let ieee_mantissa = bits & ((1u64 << 52) - 1);
+    let ieee_exponent = (bits >> 52) as u32 & 0x7ff;
+    if ieee_exponent == 0x7ff || ieee_exponent == 0 && ieee_mantissa == 0 {
+        return;
+    }
+    let ryu = d2s::d2d(ieee_mantissa, ieee_exponent);
+    let schubfach = schubfach::d2d(ieee_mantissa, ieee_exponent);
+    assert_eq!(
+        (ryu.mantissa, ryu.exponent),
+        (schubfach.mantissa, schubfach.exponent),
+        "{:e}",
+        f64::from_bits(bits),
+    );
+}
+
+fn check32(bits: u32) {
+    let ieee_mantissa = bits & ((1u32 << 23) - 1);
+    let ieee_exponent = (bits >> 23) & 0xff;
+    if ieee_exponent == 0xff || ieee_exponent == 0 && ieee_mantissa == 0 {
+        return;
+    }
+    let ryu = f2s::f2d(ieee_mantissa, ieee_exponent);
+    let schubfach = schubfach::f2d(ieee_mantissa, ieee_exponent);
+    assert_eq!(
+        (ryu.mantissa, ryu.exponent),
+        (schubfach.mantissa, schubfach.exponent),
+        "{:e}",
+        f32::from_bits(bits),
+    );
+}
+
+#[test]
+fn test_every_exponent() {
+    for ieee_exponent in 0..0x7ff_u64 {
+        for ieee_mantissa in [
+            0,
+            1,
+            2,
+            3,
+            0xfffffffffffff,
+            0xffffffffffffe,
+            0x8000000000000,
+        ] {
+            check64(ieee_exponent << 52 | ieee_mantissa);
+        }
+    }
+    for ieee_exponent in 0..0xff_u32 {
+        for ieee_mantissa in [0, 1, 2, 3, 0x7fffff, 0x7ffffe, 0x400000] {
+            check32(ieee_exponent << 23 | ieee_mantissa);
+        }
+    }
+}
+
+#[test]
+fn test_powers_of_ten() {
+    let mut f = 1e-323f64;
+    while f.is_finite() {
+        for ulp in [-1i64, 0, 1] {
+            check64(f.to_bits().wrapping_add(ulp as u64));
+        }
+        f *= 10.0;
+    }
+    let mut f = 1e-45f32;
+    while f.is_finite() {
+        for ulp in [-1i32, 0, 1] {
+            check32(f.to_bits().wrapping_add(ulp as u32));
+        }
+        f *= 10.0;
+    }
+}
+
+#[test]
+fn test_random() {
+    let n = if cfg!(miri) { 100 } else { 1000000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        check64(rng.random());
+        check32(rng.random());
+    }
+}
+
+// A deliberately simple arbitrary precision integer, little-endian in 32-bit
+// limbs, to check the generated tables against.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Big(Vec<u32>);
+
+impl Big {
+    fn from_u128(mut n: u128) -> Self {
+        let mut limbs = Vec::new();
+        while n != 0 {
+            limbs.push(n as u32);
+            n >>= 32;
+        }
+        Big(limbs)
+    }
+
+    fn pow5(i: u32) -> Self {
+        let mut big = Big::from_u128(1);
+        for _ in 0..i {
+            big = big.mul(&Big::from_u128(5));
+        }
+        big
+    }
+
+    fn pow2(i: u32) -> Self {
+        let mut limbs = vec![0; i as usize / 32];
+        limbs.push(1 << (i % 32));
+        Big(limbs)
+    }
+
+    fn bits(&self) -> u32 {
+        match self.0.last() {
+            Some(top) => 32 * self.0.len() as u32 - top.leading_zeros(),
+            None => 0,
+        }
+    }
+
+    fn mul(&self, other: &Big) -> Big {
+        let mut limbs = vec![0u32; self.0.len() + other.0.len()];
+        for (i, &a) in self.0.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.0.iter().enumerate() {
+                let sum = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            limbs[i + other.0.len()] = carry as u32;
+        }
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Big(limbs)
+    }
+}
+
+impl PartialOrd for Big {
+    fn partial_cmp(&self, other: &Big) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Big {
+    fn cmp(&self, other: &Big) -> Ordering {
+        let by_len = self.0.len().cmp(&other.0.len());
+        by_len.then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
+
+// value == ceil(10^e / 2^(floor(log2(10^e)) + 1 - bits)), which has its top
+// bit set.
+fn check_pow10_significand(e: i32, value: u128, bits: u32) {
+    assert_eq!(128 - value.leading_zeros(), bits, "10^{e}");
+    let pow5 = Big::pow5(e.unsigned_abs());
+    let pow10 = pow5.mul(&Big::pow2(e.unsigned_abs()));
+    // 10^-e is never a power of 2 for e < 0, so its log2 is not an integer.
+    let floor_log2 = if e >= 0 {
+        pow10.bits() as i32 - 1
+    } else {
+        -(pow10.bits() as i32)
+    };
+    // 10^e / 2^(floor_log2 + 1 - bits) == 5^e * 2^shift == num / den
+    let shift = e + bits as i32 - 1 - floor_log2;
+    let (mut num, mut den) = (Big::pow2(0), Big::pow2(0));
+    if e >= 0 {
+        num = pow5;
+    } else {
+        den = pow5;
+    }
+    if shift >= 0 {
+        num = num.mul(&Big::pow2(shift as u32));
+    } else {
+        den = den.mul(&Big::pow2(-shift as u32));
+    }
+    assert!(Big::from_u128(value - 1).mul(&den) < num, "10^{e}");
+    assert!(num <= Big::from_u128(value).mul(&den), "10^{e}");
+}
+
+#[test]
+fn test_pow10_significands() {
+    for e in schubfach::POW10_MIN_EXPONENT64..=schubfach::POW10_MAX_EXPONENT64 {
+        let (lo, hi) = schubfach::compute_pow10_significand64(e);
+        check_pow10_significand(e, lo as u128 | (hi as u128) << 64, 128);
+    }
+    for e in schubfach::POW10_MIN_EXPONENT32..=schubfach::POW10_MAX_EXPONENT32 {
+        let value = schubfach::compute_pow10_significand32(e);
+        check_pow10_significand(e, value as u128, 64);
+    }
+}
+
+#[cfg(not(feature = "tiny"))]
+#[test]
+fn test_pow10_tables() {
+    for (i, &entry) in schubfach::POW10_SIGNIFICANDS64.iter().enumerate() {
+        let e = schubfach::POW10_MIN_EXPONENT64 + i as i32;
+        assert_eq!(entry, schubfach::compute_pow10_significand64(e), "10^{e}");
+    }
+    for (i, &entry) in schubfach::POW10_SIGNIFICANDS32.iter().enumerate() {
+        let e = schubfach::POW10_MIN_EXPONENT32 + i as i32;
+        assert_eq!(entry, schubfach::compute_pow10_significand32(e), "10^{e}");
+    }
+}
+
+#[test]
+fn test_const_agrees() {
+    let n = if cfg!(miri) { 100 } else { 100000 };
+    let mut rng = rand::rng();
+    for _ in 0..n {
+        let bits: u64 = rng.random();
+        let ieee_mantissa = bits & ((1u64 << 52) - 1);
+        let ieee_exponent = (bits >> 52) as u32 & 0x7ff;
+        if ieee_exponent != 0x7ff && (ieee_exponent != 0 || ieee_mantissa != 0) {
+            let table = schubfach::d2d(ieee_mantissa, ieee_exponent);
+            let computed = schubfach::d2d_const(ieee_mantissa, ieee_exponent);
+            assert_eq!(
+                (table.mantissa, table.exponent),
+                (computed.mantissa, computed.exponent),
+            );
+        }
+
+        let bits: u32 = rng.random();
+        let ieee_mantissa = bits & ((1u32 << 23) - 1);
+        let ieee_exponent = (bits >> 23) & 0xff;
+        if ieee_exponent != 0xff && (ieee_exponent != 0 || ieee_mantissa != 0) {
+            let table = schubfach::f2d(ieee_mantissa, ieee_exponent);
+            let computed = schubfach::f2d_const(ieee_mantissa, ieee_exponent);
+            assert_eq!(
+                (table.mantissa, table.exponent),
+                (computed.mantissa, computed.exponent),
+            );
+        }
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "serde")]
+#![allow(clippy::float_cmp)]
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AsStr {
+    #[serde(with = "ryu::serde::as_str")]
+    f64: f64,
+    #[serde(with = "ryu::serde::as_str")]
+    f32: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Finite {
+    #[serde(with = "ryu::serde::as_str::finite")]
+    f64: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct NullNonFinite {
+    #[serde(with = "ryu::serde::as_str::null_nonfinite")]
+    f64: f64,
+}
+
+#[test]
+fn test_as_str() {
+    let value = AsStr {
+        f64: 1.234e-300,
+        f32: -0.3,
+    };
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, r#"{"f64":"1.234e-300","f32":"-0.3"}"#);
+    let back: AsStr = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.f64, value.f64);
+    assert_eq!(back.f32, value.f32);
+}
+
+#[test]
+fn test_as_str_nonfinite() {
+    let value = AsStr {
+        f64: f64::NAN,
+        f32: f32::NEG_INFINITY,
+    };
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, r#"{"f64":"NaN","f32":"-inf"}"#);
+    let back: AsStr = serde_json::from_str(&json).unwrap();
+    assert!(back.f64.is_nan());
+    assert_eq!(back.f32, f32::NEG_INFINITY);
+}
+
+#[test]
+fn test_as_str_invalid() {
+    let err = serde_json::from_str::<AsStr>(r#"{"f64":"1x","f32":"0"}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"1x\", expected a floating point number as a string at line 1 column 11",
+    );
+    let err = serde_json::from_str::<AsStr>(r#"{"f64":1.0,"f32":"0"}"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("invalid type: floating point `1.0`"));
+    // More significant digits than ryu::parse accepts.
+    let json = r#"{"f64":"3.14159265358979323846","f32":"0"}"#;
+    assert!(serde_json::from_str::<AsStr>(json).is_err());
+    let json = r#"{"f64":"0","f32":"3.1415926536"}"#;
+    assert!(serde_json::from_str::<AsStr>(json).is_err());
+}
+
+#[test]
+fn test_finite() {
+    let json = serde_json::to_string(&Finite { f64: 1e30 }).unwrap();
+    assert_eq!(json, r#"{"f64":"1e30"}"#);
+    assert_eq!(serde_json::from_str::<Finite>(&json).unwrap().f64, 1e30);
+
+    let err = serde_json::to_string(&Finite { f64: f64::INFINITY }).unwrap_err();
+    assert_eq!(err.to_string(), "cannot serialize non-finite float inf");
+    assert!(serde_json::from_str::<Finite>(r#"{"f64":"NaN"}"#).is_err());
+    assert!(serde_json::from_str::<Finite>(r#"{"f64":"1e999"}"#).is_err());
+}
+
+#[test]
+fn test_null_nonfinite() {
+    let json = serde_json::to_string(&NullNonFinite { f64: 0.1 }).unwrap();
+    assert_eq!(json, r#"{"f64":"0.1"}"#);
+    assert_eq!(
+        serde_json::from_str::<NullNonFinite>(&json).unwrap().f64,
+        0.1
+    );
+
+    let json = serde_json::to_string(&NullNonFinite { f64: f64::NAN }).unwrap();
+    assert_eq!(json, r#"{"f64":null}"#);
+    assert!(serde_json::from_str::<NullNonFinite>(&json)
+        .unwrap()
+        .f64
+        .is_nan());
+}
@@ -0,0 +1,110 @@
+#![allow(clippy::unreadable_literal)]
+
+use ryu::{NonFinite, SliceError};
+use std::fmt;
+
+#[test]
+fn test_format_slice() {
+    let floats = [1.5, -0.0, 1e300, 1.234e-300, f64::NAN, f64::INFINITY];
+    let mut out = String::new();
+    ryu::format_slice(&mut out, &floats, ", ", NonFinite::Text).unwrap();
+    assert_eq!(out, "1.5, -0.0, 1e300, 1.234e-300, NaN, inf");
+
+    let mut out = String::new();
+    ryu::format_slice(&mut out, &floats, ",", NonFinite::Null).unwrap();
+    assert_eq!(out, "1.5,-0.0,1e300,1.234e-300,null,null");
+
+    let mut out = String::new();
+    ryu::format_slice(&mut out, &floats, "\t", NonFinite::Empty).unwrap();
+    assert_eq!(out, "1.5\t-0.0\t1e300\t1.234e-300\t\t");
+
+    let mut out = String::new();
+    let result = ryu::format_slice(&mut out, &floats, ",", NonFinite::Error);
+    assert_eq!(result, Err(SliceError::NonFinite(4)));
+}
+
+#[test]
+fn test_format_slice_empty() {
+    let mut out = String::new();
+    ryu::format_slice::<_, f32>(&mut out, &[], ",", NonFinite::Error).unwrap();
+    assert_eq!(out, "");
+    ryu::format_slice(&mut out, &[f32::NEG_INFINITY], ",", NonFinite::Text).unwrap();
+    assert_eq!(out, "-inf");
+}
+
+#[test]
+fn test_format_slice_matches_buffer() {
+    // Long enough to span several chunks, with separators of various lengths
+    // including one too long to stage.
+    let floats = (0..1000u64)
+        .map(|i| f64::from_bits(0x3ff0000000000000 ^ (i.wrapping_mul(0x9e3779b97f4a7c15) >> 2)))
+        .collect::<Vec<f64>>();
+    let long = "|".repeat(600);
+    for separator in ["", ",", " | ", long.as_str()] {
+        let mut buffer = ryu::Buffer::new();
+        let expected = floats
+            .iter()
+            .map(|&f| buffer.format(f).to_owned())
+            .collect::<Vec<String>>()
+            .join(separator);
+        let mut out = String::new();
+        ryu::format_slice(&mut out, &floats, separator, NonFinite::Text).unwrap();
+        assert_eq!(out, expected);
+    }
+}
+
+#[test]
+fn test_format_slice_error() {
+    struct Full;
+
+    impl fmt::Write for Full {
+        fn write_str(&mut self, _s: &str) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    let result = ryu::format_slice(&mut Full, &[1.0f64], ",", NonFinite::Text);
+    assert_eq!(result, Err(SliceError::Fmt));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_format_slice_io() {
+    use std::io;
+
+    let mut out = Vec::new();
+    ryu::format_slice_io(&mut out, &[1.0f32, f32::NAN, 0.3], ",", NonFinite::Null).unwrap();
+    assert_eq!(out, b"1.0,null,0.3");
+
+    let error = ryu::format_slice_io(&mut out, &[f64::NAN], ",", NonFinite::Error).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "non-finite float at index 0");
+
+    let mut full = [0u8; 4];
+    let error =
+        ryu::format_slice_io(&mut &mut full[..], &[1.5, 2.5], ",", NonFinite::Text).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_append_slice() {
+    let floats = (0..200u8).map(|i| f32::from(i) / 8.0).collect::<Vec<f32>>();
+    let mut out = b"[".to_vec();
+    ryu::append_slice(&mut out, &floats, ",", NonFinite::Text).unwrap();
+    out.push(b']');
+    let mut buffer = ryu::Buffer::new();
+    let expected = floats
+        .iter()
+        .map(|&f| buffer.format(f).to_owned())
+        .collect::<Vec<String>>()
+        .join(",");
+    assert_eq!(out, format!("[{expected}]").as_bytes());
+
+    let mut floats = floats;
+    floats[150] = f32::NAN;
+    let before = out.clone();
+    let result = ryu::append_slice(&mut out, &floats, ",", NonFinite::Error);
+    assert_eq!(result, Err(SliceError::NonFinite(150)));
+    assert_eq!(out, before);
+}
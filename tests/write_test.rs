@@ -0,0 +1,144 @@
+#![allow(clippy::unreadable_literal)]
+
+use std::fmt;
+
+#[test]
+fn test_write_fmt() {
+    let mut out = String::new();
+    ryu::write_fmt(&mut out, 1.234f64).unwrap();
+    out.push(',');
+    ryu::write_fmt(&mut out, 1.234f32).unwrap();
+    out.push(',');
+    ryu::write_fmt(&mut out, f64::NAN).unwrap();
+    out.push(',');
+    ryu::write_fmt(&mut out, f32::NEG_INFINITY).unwrap();
+    out.push(',');
+    ryu::write_fmt(&mut out, 1e300).unwrap();
+    assert_eq!(out, "1.234,1.234,NaN,-inf,1e300");
+}
+
+#[test]
+fn test_write_fmt_unsized() {
+    let mut out = String::new();
+    let writer: &mut dyn fmt::Write = &mut out;
+    ryu::write_fmt(writer, -0.0f64).unwrap();
+    assert_eq!(out, "-0.0");
+}
+
+#[test]
+fn test_write_fmt_error() {
+    struct Full;
+
+    impl fmt::Write for Full {
+        fn write_str(&mut self, _s: &str) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    assert_eq!(ryu::write_fmt(&mut Full, 1.0f64), Err(fmt::Error));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_io() {
+    use std::io::{self, Write as _};
+
+    let mut out = Vec::new();
+    ryu::write_io(&mut out, 1.234f64).unwrap();
+    out.push(b',');
+    ryu::write_io(&mut out, f32::INFINITY).unwrap();
+    assert_eq!(out, b"1.234,inf");
+
+    let mut full = [0u8; 4];
+    let mut cursor = io::Cursor::new(&mut full[..]);
+    let err = ryu::write_io(&mut cursor, 1.2345f64).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+
+    assert_eq!(&full, b"1.23");
+
+    let mut full = [0u8; 4];
+    let mut cursor = io::Cursor::new(&mut full[..]);
+    ryu::write_io(&mut cursor, 0.5f64).unwrap();
+    cursor.flush().unwrap();
+    assert_eq!(&full, b"0.5\0");
+}
+
+#[test]
+fn test_format_into() {
+    let mut out = [b'_'; 32];
+    assert_eq!(ryu::format_into(1.234f64, &mut out), Some(5));
+    assert_eq!(&out[..6], b"1.234_");
+
+    for len in 0..=24 {
+        let mut out = [b'_'; 24];
+        let n = ryu::format_into(-2.2250738585072014e-308f64, &mut out[..len]);
+        if len < 24 {
+            assert_eq!(n, None);
+            assert_eq!(out, [b'_'; 24]);
+        } else {
+            assert_eq!(n, Some(24));
+            assert_eq!(&out, b"-2.2250738585072014e-308");
+        }
+    }
+
+    let mut out = [b'_'; 4];
+    assert_eq!(ryu::format_into(1.5f32, &mut out[..2]), None);
+    assert_eq!(ryu::format_into(1.5f32, &mut out[..3]), Some(3));
+    assert_eq!(&out, b"1.5_");
+    assert_eq!(ryu::format_into(f64::NEG_INFINITY, &mut out[..3]), None);
+    assert_eq!(ryu::format_into(f64::NEG_INFINITY, &mut out), Some(4));
+    assert_eq!(&out, b"-inf");
+}
+
+#[test]
+fn test_format_into_uninit() {
+    let mut vec = Vec::<u8>::with_capacity(16);
+    let n = ryu::format_into_uninit(f32::MIN_POSITIVE, vec.spare_capacity_mut()).unwrap();
+    unsafe { vec.set_len(n) };
+    assert_eq!(vec, b"1.1754944e-38");
+
+    let mut out = [std::mem::MaybeUninit::uninit(); 2];
+    assert_eq!(ryu::format_into_uninit(f64::NAN, &mut out), None);
+}
+
+#[test]
+fn test_format_into_array() {
+    let mut out = [0u8; 24];
+    let n = ryu::format_into_array(f64::MIN_POSITIVE, &mut out);
+    assert_eq!(&out[..n], b"2.2250738585072014e-308");
+    let n = ryu::format_into_array(f64::NAN, &mut out);
+    assert_eq!(&out[..n], b"NaN");
+
+    let mut out = [0u8; 16];
+    let n = ryu::format_into_array(-f32::MAX, &mut out);
+    assert_eq!(&out[..n], b"-3.4028235e38");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_append() {
+    let mut out = Vec::new();
+    ryu::append(&mut out, 1.234f64);
+    out.push(b',');
+    ryu::append(&mut out, -f32::MAX);
+    out.push(b',');
+    ryu::append(&mut out, f64::INFINITY);
+    assert_eq!(out, b"1.234,-3.4028235e38,inf");
+
+    let mut out = Vec::with_capacity(24);
+    ryu::append(&mut out, -2.2250738585072014e-308f64);
+    assert_eq!(out, b"-2.2250738585072014e-308");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_append_string() {
+    let mut out = String::from("[");
+    for f in [0.1f64, 1e100, f64::NAN] {
+        ryu::append_string(&mut out, f);
+        out.push(',');
+    }
+    out.pop();
+    out.push(']');
+    assert_eq!(out, "[0.1,1e100,NaN]");
+}